@@ -0,0 +1,436 @@
+use bevy::prelude::*;
+
+use crate::{
+    ai::{
+        behavior::{AttackBehavior, Behavior, CurrentBehavior},
+        command::CurrentCommand,
+        targeting::Target,
+        threat::{Taunted, ThreatTable},
+    },
+    collision::Collider,
+    combat::{
+        area::{AreaAttack, AreaDamageEvent, AreaOrigin, AreaShape, FriendlyFire},
+        damage::{DamageEvent, DamageKind, HealEvent},
+        status::{ApplyStatusEffect, StatusEffect},
+    },
+    units::{health::Health, team::CurrentTeam},
+    velocity::Velocity,
+};
+
+// How long a dash can go on before it gives up on reaching the target
+const DASH_MAX_DURATION: f32 = 1.0;
+const DASH_ARRIVE_MARGIN: f32 = 8.0;
+
+// Who the AI looks for before it starts casting
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AbilityTargeting {
+    Enemy,                                // The unit's current target
+    WoundedAlly { health_fraction: f32 }, // The most wounded friendly unit below the fraction
+    NearbyEnemies { count: usize },       // Cast on itself when enough enemies are in range
+}
+
+#[derive(Debug, Clone)]
+pub enum AbilityEffect {
+    Damage {
+        amount: u32,
+        kind: DamageKind,
+    },
+    Status(StatusEffect),
+    Heal {
+        amount: u32,
+    },
+    Taunt {
+        radius: f32,
+        duration: f32,
+        threat: f32,
+    },
+    // Hits every enemy around the caster rather than just the target
+    Nova {
+        radius: f32,
+        amount: u32,
+        kind: DamageKind,
+    },
+    // The effects after the dash are applied once the caster reaches the target
+    Dash {
+        speed: f32,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct Ability {
+    pub name: &'static str,
+    pub cast_time: f32,
+    pub range: f32,
+    pub min_range: f32,
+    pub targeting: AbilityTargeting,
+    pub effects: Vec<AbilityEffect>,
+    pub cooldown_timer: Timer,
+}
+
+impl Ability {
+    pub fn new(
+        name: &'static str,
+        cooldown: f32,
+        cast_time: f32,
+        range: f32,
+        targeting: AbilityTargeting,
+        effects: Vec<AbilityEffect>,
+    ) -> Self {
+        Ability {
+            name,
+            cast_time,
+            range,
+            min_range: 0.0,
+            targeting,
+            effects,
+            cooldown_timer: Timer::from_seconds(cooldown, TimerMode::Once),
+        }
+    }
+
+    pub fn with_min_range(mut self, min_range: f32) -> Self {
+        self.min_range = min_range;
+        self
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.cooldown_timer.finished()
+    }
+}
+
+#[derive(Component, Default, Clone, Debug)]
+pub struct Abilities(pub Vec<Ability>);
+
+#[derive(Component, Clone, Debug)]
+pub struct Casting {
+    pub ability_index: usize,
+    pub target: Entity,
+    pub timer: Timer,
+}
+
+#[derive(Component, Clone, Debug)]
+pub struct Dashing {
+    pub target: Entity,
+    pub speed: f32,
+    pub effects: Vec<AbilityEffect>,
+    pub timer: Timer,
+}
+
+// The effects of an ability that has finished casting, or of a dash that has arrived
+#[derive(Event, Clone, Debug)]
+pub struct AbilityEffectEvent {
+    pub caster: Entity,
+    pub target: Entity,
+    pub effects: Vec<AbilityEffect>,
+}
+
+pub fn tick_ability_cooldowns(time: Res<Time>, mut query: Query<&mut Abilities>) {
+    for mut abilities in query.iter_mut() {
+        for ability in abilities.0.iter_mut() {
+            ability.cooldown_timer.tick(time.delta());
+        }
+    }
+}
+
+fn get_distance(transform: &Transform, other_transform: &Transform) -> f32 {
+    (other_transform.translation.truncate() - transform.translation.truncate()).length()
+}
+
+#[allow(clippy::type_complexity)]
+pub fn choose_abilities(
+    mut commands: Commands,
+    mut query: Query<
+        (
+            Entity,
+            &mut Abilities,
+            &CurrentBehavior,
+            &Transform,
+            &CurrentTeam,
+            &Health,
+            Option<&Target>,
+            Option<&CurrentCommand>,
+        ),
+        (Without<Casting>, Without<Dashing>),
+    >,
+    others_query: Query<(Entity, &Transform, &CurrentTeam, &Health)>,
+) {
+    for (
+        entity,
+        mut abilities,
+        current_behavior,
+        transform,
+        team,
+        health,
+        target,
+        current_command,
+    ) in query.iter_mut()
+    {
+        if health.is_dead()
+            || matches!(
+                current_behavior.0,
+                Behavior::Stunned(_) | Behavior::Feared(_)
+            )
+        {
+            continue;
+        }
+
+        let allows_attack = current_command.is_none_or(|command| command.allows_attack());
+        let is_in_range = |ability: &Ability, other_transform: &Transform| {
+            let distance = get_distance(transform, other_transform);
+            distance >= ability.min_range && distance <= ability.range
+        };
+
+        let chosen = abilities.0.iter().enumerate().find_map(|(index, ability)| {
+            if !ability.is_ready() {
+                return None;
+            }
+
+            let ability_target = match ability.targeting {
+                AbilityTargeting::Enemy => target
+                    .and_then(|target| target.0)
+                    .filter(|_| allows_attack)
+                    .and_then(|target| others_query.get(target).ok())
+                    .filter(|(_, other_transform, _, other_health)| {
+                        !other_health.is_dead() && is_in_range(ability, other_transform)
+                    })
+                    .map(|(other, _, _, _)| other),
+                AbilityTargeting::WoundedAlly { health_fraction } => others_query
+                    .iter()
+                    .filter(|(_, other_transform, other_team, other_health)| {
+                        team.is_friendly(other_team)
+                            && !other_health.is_dead()
+                            && other_health.fraction() < health_fraction
+                            && is_in_range(ability, other_transform)
+                    })
+                    .min_by(|a, b| a.3.fraction().total_cmp(&b.3.fraction()))
+                    .map(|(other, _, _, _)| other),
+                AbilityTargeting::NearbyEnemies { count } => {
+                    let enemies_in_range = others_query
+                        .iter()
+                        .filter(|(_, other_transform, other_team, other_health)| {
+                            !team.is_friendly(other_team)
+                                && !other_health.is_dead()
+                                && is_in_range(ability, other_transform)
+                        })
+                        .count();
+                    (allows_attack && enemies_in_range >= count).then_some(entity)
+                }
+            };
+
+            ability_target.map(|ability_target| (index, ability_target))
+        });
+
+        let Some((ability_index, ability_target)) = chosen else {
+            continue;
+        };
+
+        let ability = &mut abilities.0[ability_index];
+        ability.cooldown_timer.reset();
+        commands.entity(entity).insert(Casting {
+            ability_index,
+            target: ability_target,
+            timer: Timer::from_seconds(ability.cast_time, TimerMode::Once),
+        });
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn execute_behavior_casting(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(
+        Entity,
+        &CurrentBehavior,
+        &mut Casting,
+        &Abilities,
+        &mut Velocity,
+        Option<&mut AttackBehavior>,
+    )>,
+    mut ability_effect_events: EventWriter<AbilityEffectEvent>,
+) {
+    for (entity, current_behavior, mut casting, abilities, mut velocity, attack_behavior) in
+        query.iter_mut()
+    {
+        // Anything that takes over the behavior, like a stun, interrupts the cast
+        let Behavior::Casting(_) = current_behavior.0 else {
+            commands.entity(entity).remove::<Casting>();
+            continue;
+        };
+
+        velocity.0 = Vec2::ZERO;
+        if !casting.timer.tick(time.delta()).finished() {
+            continue;
+        }
+
+        commands.entity(entity).remove::<Casting>();
+        let Some(ability) = abilities.0.get(casting.ability_index) else {
+            continue;
+        };
+
+        if let Some(mut attack_behavior) = attack_behavior {
+            attack_behavior.is_attacking = true;
+        }
+
+        ability_effect_events.send(AbilityEffectEvent {
+            caster: entity,
+            target: casting.target,
+            effects: ability.effects.clone(),
+        });
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn execute_dashing(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<
+        (
+            Entity,
+            &CurrentBehavior,
+            &mut Dashing,
+            &mut Transform,
+            &Collider,
+            &mut Velocity,
+        ),
+        Without<Casting>,
+    >,
+    target_query: Query<(&Transform, &Collider, &Health), Without<Dashing>>,
+    mut ability_effect_events: EventWriter<AbilityEffectEvent>,
+) {
+    for (entity, current_behavior, mut dashing, mut transform, collider, mut velocity) in
+        query.iter_mut()
+    {
+        let is_interrupted = !matches!(current_behavior.0, Behavior::Casting(_));
+        let target = target_query
+            .get(dashing.target)
+            .ok()
+            .filter(|(_, _, target_health)| !target_health.is_dead());
+
+        let Some((target_transform, target_collider, _)) = target else {
+            commands.entity(entity).remove::<Dashing>();
+            continue;
+        };
+
+        if is_interrupted || dashing.timer.tick(time.delta()).finished() {
+            commands.entity(entity).remove::<Dashing>();
+            continue;
+        }
+
+        velocity.0 = Vec2::ZERO;
+        let offset = target_transform.translation.truncate() - transform.translation.truncate();
+        let arrive_distance = collider.radius + target_collider.radius + DASH_ARRIVE_MARGIN;
+        let step = dashing.speed * time.delta_seconds();
+        if offset.length() - step > arrive_distance {
+            transform.translation += (offset.normalize_or_zero() * step).extend(0.0);
+            continue;
+        }
+
+        transform.translation +=
+            (offset.normalize_or_zero() * (offset.length() - arrive_distance).max(0.0)).extend(0.0);
+        commands.entity(entity).remove::<Dashing>();
+        ability_effect_events.send(AbilityEffectEvent {
+            caster: entity,
+            target: dashing.target,
+            effects: dashing.effects.clone(),
+        });
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn resolve_ability_effects(
+    mut commands: Commands,
+    mut ability_effect_events: EventReader<AbilityEffectEvent>,
+    mut damage_events: EventWriter<DamageEvent>,
+    mut status_events: EventWriter<ApplyStatusEffect>,
+    mut heal_events: EventWriter<HealEvent>,
+    mut area_damage_events: EventWriter<AreaDamageEvent>,
+    caster_query: Query<(&Transform, &CurrentTeam)>,
+    mut others_query: Query<(Entity, &Transform, &CurrentTeam, &Health, &mut ThreatTable)>,
+) {
+    for event in ability_effect_events.read() {
+        for (index, effect) in event.effects.iter().enumerate() {
+            match effect {
+                AbilityEffect::Damage { amount, kind } => {
+                    damage_events.send(DamageEvent {
+                        source: Some(event.caster),
+                        target: event.target,
+                        amount: *amount,
+                        kind: *kind,
+                    });
+                }
+                AbilityEffect::Status(status_effect) => {
+                    status_events.send(ApplyStatusEffect {
+                        source: Some(event.caster),
+                        target: event.target,
+                        effect: status_effect.clone(),
+                    });
+                }
+                AbilityEffect::Heal { amount } => {
+                    heal_events.send(HealEvent {
+                        source: Some(event.caster),
+                        target: event.target,
+                        amount: *amount,
+                    });
+                }
+                AbilityEffect::Taunt {
+                    radius,
+                    duration,
+                    threat,
+                } => {
+                    let Ok((caster_transform, caster_team)) = caster_query.get(event.caster) else {
+                        continue;
+                    };
+
+                    for (other, other_transform, other_team, other_health, mut threat_table) in
+                        others_query.iter_mut()
+                    {
+                        if caster_team.is_friendly(other_team)
+                            || other_health.is_dead()
+                            || get_distance(caster_transform, other_transform) > *radius
+                        {
+                            continue;
+                        }
+
+                        threat_table.add(event.caster, *threat);
+                        commands.entity(other).insert(Taunted {
+                            taunter: event.caster,
+                            timer: Timer::from_seconds(*duration, TimerMode::Once),
+                        });
+                    }
+                }
+                AbilityEffect::Nova {
+                    radius,
+                    amount,
+                    kind,
+                } => {
+                    let Ok((caster_transform, _)) = caster_query.get(event.caster) else {
+                        continue;
+                    };
+
+                    let area = AreaAttack {
+                        shape: AreaShape::Circle { radius: *radius },
+                        origin: AreaOrigin::Attacker,
+                        falloff: 0.0,
+                        friendly_fire: FriendlyFire::Disabled,
+                    };
+                    let position = caster_transform.translation.truncate();
+                    area_damage_events.send(area.to_event(
+                        Some(event.caster),
+                        position,
+                        position,
+                        *amount,
+                        *kind,
+                    ));
+                }
+                AbilityEffect::Dash { speed } => {
+                    commands.entity(event.caster).insert(Dashing {
+                        target: event.target,
+                        speed: *speed,
+                        effects: event.effects[index + 1..].to_vec(),
+                        timer: Timer::from_seconds(DASH_MAX_DURATION, TimerMode::Once),
+                    });
+                    break;
+                }
+            }
+        }
+    }
+}
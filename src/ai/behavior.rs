@@ -1,33 +1,70 @@
 use bevy::prelude::*;
-use rand::Rng;
 
 use crate::{
-    dark_arts_defense::{GameEvent, RandomSeed},
-    units::{
-        health::Health,
-        team::{CurrentTeam, Team},
+    ai::{
+        ability::{Abilities, Casting, Dashing},
+        command::{CurrentCommand, UnitCommand},
+        steering::Steering,
+        targeting::{Target, TargetSelection},
+        threat::ThreatTable,
     },
+    combat::{
+        area::{AreaAttack, AreaDamageEvent},
+        damage::{roll_attack_damage, DamageEvent, DamageKind, HealEvent},
+        projectile::{spawn_projectile, ProjectileLaunch, ProjectileParams},
+        status::{StatusEffectKind, StatusEffects},
+    },
+    dark_arts_defense::RandomSeed,
+    player::plugin::Player,
+    units::{health::Health, team::CurrentTeam},
     velocity::Velocity,
 };
 
-const ATTACK_DISTANCE_MAX: f32 = 96.0;
-const ATTACK_DISTANCE_MID: f32 = 72.0;
-const ATTACK_DISTANCE_MIN: f32 = 48.0;
+// Melee units stop moving once the target is closer than this fraction of the engage distance
+const ATTACK_STOP_FRACTION: f32 = 0.66;
+
+const FORMATION_RING_SPACING: f32 = 72.0;
+const FORMATION_FIRST_RING_SLOTS: usize = 6;
+
+const COMMAND_ARRIVE_DISTANCE: f32 = 32.0;
 
 #[derive(Clone, Debug)]
 pub enum Behavior {
-    Idle(IdleBehavior),           // Do nothing
-    MoveOrigo(MoveOrigoBehavior), // Special case for enemies with no targets in range, move towards origo instead
-    Wander(WanderBehavior),       // Friendly units wander around when waiting for enemies
-    Chase(ChaseBehavior),         // Both friendly and enemy units chase their targets
-    Flee(FleeBehavior),           // The acolyte tries to flee from enemies
-    Attack(AttackBehavior),       // Attack when in range
-    Dead(DeadBehavior),           // Dead units do nothing
+    // Do nothing
+    Idle(IdleBehavior),
+    // Special case for enemies with no targets in range, move towards origo instead
+    MoveOrigo(MoveOrigoBehavior),
+    // Enemies wander around when waiting for targets
+    Wander(WanderBehavior),
+    // Summons keep their formation slot around the player
+    Follow(FollowBehavior),
+    // Summons keep their formation slot around a guard point
+    Guard(GuardBehavior),
+    // Summons carry out the command the player gave them
+    Command(CommandBehavior),
+    // Both friendly and enemy units chase their targets
+    Chase(ChaseBehavior),
+    // The acolyte tries to flee from enemies
+    Flee(FleeBehavior),
+    // Supports seek out wounded allies and heal them over time
+    Heal(HealBehavior),
+    // Attack when in range
+    Attack(AttackBehavior),
+    // Shoot projectiles from a distance when in range
+    RangedAttack(RangedAttackBehavior),
+    // Dead units do nothing
+    Dead(DeadBehavior),
+    // Forced while casting an ability, until the ability goes off
+    Casting(CastingBehavior),
+    // Forced by a stun, the unit can't do anything until it wears off
+    Stunned(StunnedBehavior),
+    // Forced by fear, the unit runs away from whoever scared it
+    Feared(FearedBehavior),
 }
 
 impl Default for Behavior {
     fn default() -> Self {
-        Behavior::Wander(WanderBehavior::default())
+        Behavior::Follow(FollowBehavior::default())
     }
 }
 
@@ -62,18 +99,69 @@ impl Default for WanderBehavior {
     }
 }
 
+// The anchor is the player, or the guard point if the unit has one. Units that stray further
+// than the leash distance from it drop their target and return.
+#[derive(Component, Clone, Debug)]
+pub struct FollowBehavior {
+    pub slot_index: usize,
+    pub slot_offset: Vec2,
+    pub arrive_distance: f32,
+    pub leash_distance: f32,
+    pub anchor: Vec2,
+    pub is_returning: bool,
+}
+
+impl Default for FollowBehavior {
+    fn default() -> Self {
+        FollowBehavior {
+            slot_index: 0,
+            slot_offset: Vec2::ZERO,
+            arrive_distance: 24.0,
+            leash_distance: 450.0,
+            anchor: Vec2::ZERO,
+            is_returning: false,
+        }
+    }
+}
+
+#[derive(Component, Clone, Copy, Debug, Default)]
+pub struct GuardBehavior {
+    pub point: Option<Vec2>,
+}
+
 #[derive(Component, Clone, Copy, Debug)]
-pub struct ChaseBehavior {}
+pub struct CommandBehavior {}
 
 #[derive(Component, Clone, Copy, Debug)]
-pub struct FleeBehavior {}
+pub struct ChaseBehavior {
+    pub radius: f32, // How far away targets are noticed
+}
+
+impl Default for ChaseBehavior {
+    fn default() -> Self {
+        ChaseBehavior { radius: 768.0 }
+    }
+}
+
+#[derive(Component, Clone, Copy, Debug)]
+pub struct FleeBehavior {
+    pub radius: f32, // How close enemies can get before the unit runs
+}
+
+impl Default for FleeBehavior {
+    fn default() -> Self {
+        FleeBehavior { radius: 288.0 }
+    }
+}
 
 #[derive(Component, Clone, Debug)]
 pub struct AttackBehavior {
+    pub range: f32,           // Targets closer than this can be attacked
+    pub engage_distance: f32, // The distance the unit tries to keep while attacking
     pub cooldown: f32,
     pub random_cooldown_offset: f32,
-    pub random_attack_offset: u8,
-    pub damage: u8,
+    pub random_attack_offset: u32,
+    pub damage: u32,
     pub is_attacking: bool,
     pub timer: Timer,
 }
@@ -82,6 +170,8 @@ impl Default for AttackBehavior {
     fn default() -> Self {
         let attack_cooldown = 4.0;
         AttackBehavior {
+            range: 96.0,
+            engage_distance: 72.0,
             cooldown: attack_cooldown,
             random_cooldown_offset: 0.5,
             random_attack_offset: 5,
@@ -92,9 +182,60 @@ impl Default for AttackBehavior {
     }
 }
 
+#[derive(Component, Clone, Debug)]
+pub struct HealBehavior {
+    pub radius: f32,          // How far away wounded allies are noticed
+    pub range: f32,           // Allies closer than this are healed
+    pub health_fraction: f32, // Allies below this fraction of their max health count as wounded
+    pub amount: u32,          // Healed every tick
+    pub timer: Timer,
+}
+
+impl Default for HealBehavior {
+    fn default() -> Self {
+        HealBehavior {
+            radius: 400.0,
+            range: 160.0,
+            health_fraction: 0.9,
+            amount: 4,
+            timer: Timer::from_seconds(0.5, TimerMode::Repeating),
+        }
+    }
+}
+
+// Ranged units also get the attack as a regular AttackBehavior component, that is what
+// stats, animations and cooldowns work with
+#[derive(Component, Clone, Debug)]
+pub struct RangedAttackBehavior {
+    pub attack: AttackBehavior,
+    pub projectile: ProjectileParams,
+}
+
+impl Default for RangedAttackBehavior {
+    fn default() -> Self {
+        RangedAttackBehavior {
+            attack: AttackBehavior {
+                range: 320.0,
+                engage_distance: 240.0,
+                ..default()
+            },
+            projectile: ProjectileParams::default(),
+        }
+    }
+}
+
 #[derive(Component, Clone, Debug)]
 pub struct DeadBehavior;
 
+#[derive(Component, Clone, Copy, Debug)]
+pub struct CastingBehavior {}
+
+#[derive(Component, Clone, Copy, Debug)]
+pub struct StunnedBehavior {}
+
+#[derive(Component, Clone, Copy, Debug)]
+pub struct FearedBehavior {}
+
 #[derive(Component, Default, Clone)]
 pub struct CurrentBehavior(pub Behavior);
 
@@ -104,8 +245,10 @@ pub struct SupportedBehaviors(pub Vec<(Behavior, u8)>);
 impl Default for SupportedBehaviors {
     fn default() -> Self {
         SupportedBehaviors(vec![
-            (Behavior::Wander(WanderBehavior::default()), 5),
-            (Behavior::Chase(ChaseBehavior {}), 10),
+            (Behavior::Follow(FollowBehavior::default()), 5),
+            (Behavior::Guard(GuardBehavior::default()), 6),
+            (Behavior::Command(CommandBehavior {}), 8),
+            (Behavior::Chase(ChaseBehavior::default()), 10),
             (Behavior::Attack(AttackBehavior::default()), 15),
             (Behavior::Dead(DeadBehavior {}), 20),
         ])
@@ -116,17 +259,38 @@ impl Default for SupportedBehaviors {
 pub struct BehaviorBundle {
     pub current_behavior: CurrentBehavior,
     pub supported_behaviors: SupportedBehaviors,
+    pub steering: Steering,
+    pub target: Target,
+    pub target_selection: TargetSelection,
+    pub threat_table: ThreatTable,
+    pub current_command: CurrentCommand,
+    pub abilities: Abilities,
 }
 
-fn get_flee_distance(window: &Window) -> f32 {
-    window.width() * 0.15
+// Slots are laid out in rings around the anchor, each ring fits a few more units than the one inside
+pub fn get_formation_slot(index: usize) -> Vec2 {
+    let mut ring = 1;
+    let mut first_index_in_ring = 0;
+    loop {
+        let slots_in_ring = FORMATION_FIRST_RING_SLOTS * ring;
+        if index < first_index_in_ring + slots_in_ring {
+            let angle =
+                std::f32::consts::TAU * (index - first_index_in_ring) as f32 / slots_in_ring as f32;
+            return Vec2::from_angle(angle) * FORMATION_RING_SPACING * ring as f32;
+        }
+
+        first_index_in_ring += slots_in_ring;
+        ring += 1;
+    }
 }
 
-fn get_chase_distance(window: &Window) -> f32 {
-    window.width() * 0.4
+// The lowest slot no living follower holds, so slots freed by the dead get filled first
+pub fn get_free_formation_slot(used_slots: impl Iterator<Item = usize>) -> usize {
+    let used_slots = used_slots.collect::<Vec<usize>>();
+    (0..).find(|index| !used_slots.contains(index)).unwrap_or(0)
 }
 
-fn is_other_valid_target(
+pub fn is_other_valid_target(
     team: &CurrentTeam,
     other_health: &Health,
     other_team: &CurrentTeam,
@@ -147,19 +311,74 @@ fn is_other_valid_target(
     distance_to_other.length() < distance
 }
 
+// The friendly unit with the lowest health fraction the healer would help, never the healer itself
+pub fn get_most_wounded_ally<'a>(
+    entity: Entity,
+    team: &CurrentTeam,
+    transform: &Transform,
+    heal_behavior: &HealBehavior,
+    allies: impl IntoIterator<Item = (Entity, &'a Transform, &'a CurrentTeam, &'a Health)>,
+) -> Option<(Entity, &'a Transform)> {
+    allies
+        .into_iter()
+        .filter(|(ally, ally_transform, ally_team, ally_health)| {
+            *ally != entity
+                && team.is_friendly(ally_team)
+                && !ally_health.is_dead()
+                && ally_health.fraction() < heal_behavior.health_fraction
+                && (ally_transform.translation.truncate() - transform.translation.truncate())
+                    .length()
+                    < heal_behavior.radius
+        })
+        .min_by(|a, b| a.3.fraction().total_cmp(&b.3.fraction()))
+        .map(|(ally, ally_transform, _, _)| (ally, ally_transform))
+}
+
+#[allow(clippy::type_complexity)]
 pub fn behavior_state_machine(
     mut query: Query<(
+        Entity,
         &mut CurrentBehavior,
         &SupportedBehaviors,
         &Transform,
         &CurrentTeam,
         &Health,
+        Option<&Target>,
+        Option<&FollowBehavior>,
+        Option<&GuardBehavior>,
+        Option<&CurrentCommand>,
+        Option<&StatusEffects>,
+        Has<Casting>,
+        Has<Dashing>,
     )>,
     others_query: Query<(&Transform, &CurrentTeam, &Health)>,
+    allies_query: Query<(Entity, &Transform, &CurrentTeam, &Health)>,
     window_query: Query<&Window>,
 ) {
-    for (mut current_behavior, supported_behaviors, transform, team, health) in query.iter_mut() {
-        let window = &window_query.single();
+    for (
+        entity,
+        mut current_behavior,
+        supported_behaviors,
+        transform,
+        team,
+        health,
+        target,
+        follow_behavior,
+        guard_behavior,
+        current_command,
+        status_effects,
+        is_casting,
+        is_dashing,
+    ) in query.iter_mut()
+    {
+        let is_returning = follow_behavior.is_some_and(|follow| follow.is_returning);
+        let allows_chase = current_command.is_none_or(|command| command.allows_chase());
+        let allows_attack = current_command.is_none_or(|command| command.allows_attack());
+        let target_transform = target
+            .and_then(|target| target.0)
+            .and_then(|entity| others_query.get(entity).ok())
+            .map(|(other_transform, _, _)| other_transform);
+
         let mut behaviors_that_want_to_be_active = supported_behaviors
             .0
             .iter()
@@ -173,31 +392,17 @@ pub fn behavior_state_machine(
                             distance_to_origo > window.height() * 0.3
                         }
                         (Behavior::Wander(_b), _p) => true,
-                        (Behavior::Chase(_b), _p) => others_query.iter().any(
-                            |(other_transform, other_team, other_health)| {
-                                is_other_valid_target(
-                                    team,
-                                    other_health,
-                                    other_team,
-                                    transform,
-                                    other_transform,
-                                    get_chase_distance(window),
-                                )
-                            },
-                        ),
-                        (Behavior::Flee(_b), _p) => others_query.iter().any(
-                            |(other_transform, other_team, other_health)| {
-                                is_other_valid_target(
-                                    team,
-                                    other_health,
-                                    other_team,
-                                    transform,
-                                    other_transform,
-                                    get_flee_distance(window),
-                                )
-                            },
-                        ),
-                        (Behavior::Attack(_b), _p) => others_query.iter().any(
+                        (Behavior::Follow(_b), _p) => true,
+                        (Behavior::Guard(_b), _p) => {
+                            guard_behavior.is_some_and(|guard| guard.point.is_some())
+                        }
+                        (Behavior::Command(_b), _p) => {
+                            current_command.is_some_and(|command| command.0.is_some())
+                        }
+                        (Behavior::Chase(_b), _p) => {
+                            allows_chase && !is_returning && target_transform.is_some()
+                        }
+                        (Behavior::Flee(b), _p) => others_query.iter().any(
                             |(other_transform, other_team, other_health)| {
                                 is_other_valid_target(
                                     team,
@@ -205,11 +410,38 @@ pub fn behavior_state_machine(
                                     other_team,
                                     transform,
                                     other_transform,
-                                    ATTACK_DISTANCE_MAX,
+                                    b.radius,
                                 )
                             },
                         ),
+                        (Behavior::Heal(b), _p) => {
+                            get_most_wounded_ally(entity, team, transform, b, allies_query.iter())
+                                .is_some()
+                        }
+                        (Behavior::Attack(b), _p) => {
+                            allows_attack
+                                && !is_returning
+                                && target_transform.is_some_and(|target_transform| {
+                                    (target_transform.translation.truncate()
+                                        - transform.translation.truncate())
+                                    .length()
+                                        < b.range
+                                })
+                        }
+                        (Behavior::RangedAttack(b), _p) => {
+                            allows_attack
+                                && !is_returning
+                                && target_transform.is_some_and(|target_transform| {
+                                    (target_transform.translation.truncate()
+                                        - transform.translation.truncate())
+                                    .length()
+                                        < b.attack.range
+                                })
+                        }
                         (Behavior::Dead(_b), _p) => health.is_dead(),
+                        (Behavior::Casting(_b), _p) => false,
+                        (Behavior::Stunned(_b), _p) => false,
+                        (Behavior::Feared(_b), _p) => false,
                     };
 
                 behavior_wants_to_be_active
@@ -217,10 +449,25 @@ pub fn behavior_state_machine(
             .cloned()
             .collect::<Vec<(Behavior, u8)>>();
 
-        behaviors_that_want_to_be_active.sort_by(|a, b| b.1.cmp(&a.1));
-        let highest_prio_behavior = &behaviors_that_want_to_be_active[0].0;
-
-        current_behavior.0 = highest_prio_behavior.clone();
+        behaviors_that_want_to_be_active.sort_by_key(|behavior| std::cmp::Reverse(behavior.1));
+        // Without anything that wants to be active, the unit keeps doing what it was doing
+        let highest_prio_behavior = behaviors_that_want_to_be_active
+            .first()
+            .map_or(current_behavior.0.clone(), |behavior| behavior.0.clone());
+
+        // Hard control effects override everything but dying, and interrupt any ability being cast
+        let has_status = |kind| status_effects.is_some_and(|effects| effects.has(kind));
+        current_behavior.0 = if health.is_dead() {
+            highest_prio_behavior
+        } else if has_status(StatusEffectKind::Stun) {
+            Behavior::Stunned(StunnedBehavior {})
+        } else if has_status(StatusEffectKind::Fear) {
+            Behavior::Feared(FearedBehavior {})
+        } else if is_casting || is_dashing {
+            Behavior::Casting(CastingBehavior {})
+        } else {
+            highest_prio_behavior
+        };
     }
 }
 
@@ -294,48 +541,166 @@ pub fn execute_behavior_wander(
     }
 }
 
+pub fn update_follow_anchor(
+    mut query: Query<(
+        &mut FollowBehavior,
+        Option<&GuardBehavior>,
+        Option<&CurrentCommand>,
+        &Transform,
+    )>,
+    player_query: Query<&Transform, With<Player>>,
+) {
+    let Some(player_transform) = player_query.iter().next() else {
+        return;
+    };
+
+    for (mut follow_behavior, guard_behavior, current_command, transform) in query.iter_mut() {
+        let command = current_command.and_then(|command| command.0);
+        follow_behavior.anchor = match command {
+            // Holding units stay exactly where they were told, everything else keeps formation
+            Some(UnitCommand::HoldPosition(point)) => point,
+            Some(UnitCommand::AttackMove(point) | UnitCommand::Rally(point)) => {
+                point + follow_behavior.slot_offset
+            }
+            _ => {
+                guard_behavior
+                    .and_then(|guard| guard.point)
+                    .unwrap_or(player_transform.translation.truncate())
+                    + follow_behavior.slot_offset
+            }
+        };
+
+        if current_command.is_some_and(|command| command.ignores_leash()) {
+            follow_behavior.is_returning = false;
+            continue;
+        }
+
+        let distance_to_anchor =
+            (follow_behavior.anchor - transform.translation.truncate()).length();
+        if distance_to_anchor > follow_behavior.leash_distance {
+            follow_behavior.is_returning = true;
+        } else if distance_to_anchor < follow_behavior.arrive_distance {
+            follow_behavior.is_returning = false;
+        }
+    }
+}
+
+fn move_to_anchor(follow_behavior: &FollowBehavior, transform: &Transform) -> Vec2 {
+    let direction = follow_behavior.anchor - transform.translation.truncate();
+    if direction.length() > follow_behavior.arrive_distance {
+        direction.normalize_or_zero()
+    } else {
+        Vec2::ZERO
+    }
+}
+
+pub fn execute_behavior_follow(
+    mut query: Query<(&CurrentBehavior, &FollowBehavior, &Transform, &mut Velocity)>,
+) {
+    for (current_behavior, follow_behavior, transform, mut velocity) in query.iter_mut() {
+        if let Behavior::Follow(_) = current_behavior.0 {
+            velocity.0 = move_to_anchor(follow_behavior, transform);
+        }
+    }
+}
+
+pub fn execute_behavior_guard(
+    mut query: Query<(
+        &CurrentBehavior,
+        &GuardBehavior,
+        &FollowBehavior,
+        &Transform,
+        &mut Velocity,
+    )>,
+) {
+    for (current_behavior, _, follow_behavior, transform, mut velocity) in query.iter_mut() {
+        if let Behavior::Guard(_) = current_behavior.0 {
+            velocity.0 = move_to_anchor(follow_behavior, transform);
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn execute_behavior_command(
+    mut query: Query<(
+        &CurrentBehavior,
+        &CommandBehavior,
+        &mut CurrentCommand,
+        Option<&FollowBehavior>,
+        Option<&mut GuardBehavior>,
+        &Transform,
+        &mut Velocity,
+    )>,
+    player_query: Query<&Transform, With<Player>>,
+) {
+    let player_position = player_query
+        .iter()
+        .next()
+        .map(|player_transform| player_transform.translation.truncate());
+
+    for (
+        current_behavior,
+        _,
+        mut current_command,
+        follow_behavior,
+        guard_behavior,
+        transform,
+        mut velocity,
+    ) in query.iter_mut()
+    {
+        if let Behavior::Command(_) = current_behavior.0 {
+            let Some(command) = current_command.0 else {
+                continue;
+            };
+
+            // Units in formation go to their own slot, the rest go straight to the point
+            let destination = match follow_behavior {
+                Some(follow_behavior) => Some(follow_behavior.anchor),
+                None => command.point().or(player_position),
+            };
+
+            let Some(destination) = destination else {
+                continue;
+            };
+
+            let arrive_distance =
+                follow_behavior.map_or(COMMAND_ARRIVE_DISTANCE, |follow| follow.arrive_distance);
+            let direction = destination - transform.translation.truncate();
+            if direction.length() > arrive_distance {
+                velocity.0 = direction.normalize_or_zero();
+            } else {
+                velocity.0 = Vec2::ZERO;
+                if command.completes_on_arrival() {
+                    current_command.0 = None;
+                }
+
+                if let (UnitCommand::Rally(point), Some(mut guard_behavior)) =
+                    (command, guard_behavior)
+                {
+                    guard_behavior.point = Some(point);
+                }
+            }
+        }
+    }
+}
+
 pub fn execute_behavior_chase(
     mut query: Query<(
         &CurrentBehavior,
         &ChaseBehavior,
+        &Target,
         &Transform,
-        &CurrentTeam,
         &mut Velocity,
     )>,
-    window_query: Query<&Window>,
-    others_query: Query<(&Transform, &CurrentTeam, &Health)>,
+    others_query: Query<&Transform>,
 ) {
     query
         .iter_mut()
-        .for_each(|(current_behavior, _, transform, team, mut velocity)| {
+        .for_each(|(current_behavior, _, target, transform, mut velocity)| {
             if let Behavior::Chase(_) = current_behavior.0 {
-                let window = window_query.single();
-                let mut enemies_within_range = others_query
-                    .iter()
-                    .filter(|(other_transform, other_team, other_health)| {
-                        is_other_valid_target(
-                            team,
-                            other_health,
-                            other_team,
-                            transform,
-                            other_transform,
-                            get_chase_distance(window),
-                        )
-                    })
-                    .collect::<Vec<(&Transform, &CurrentTeam, &Health)>>();
+                let target_transform = target.0.and_then(|entity| others_query.get(entity).ok());
 
-                enemies_within_range.sort_by(|a, b| {
-                    let distance_to_a =
-                        transform.translation.truncate() - a.0.translation.truncate();
-                    let distance_to_b =
-                        transform.translation.truncate() - b.0.translation.truncate();
-                    distance_to_a
-                        .length()
-                        .partial_cmp(&distance_to_b.length())
-                        .unwrap()
-                });
-
-                if let Some((enemy_transform, _t, _h)) = enemies_within_range.first() {
+                if let Some(enemy_transform) = target_transform {
                     let direction =
                         enemy_transform.translation.truncate() - transform.translation.truncate();
                     velocity.0 = direction.normalize_or_zero();
@@ -345,7 +710,6 @@ pub fn execute_behavior_chase(
 }
 
 pub fn execute_behavior_flee(
-    window_query: Query<&Window>,
     mut query: Query<(
         &CurrentBehavior,
         &FleeBehavior,
@@ -355,10 +719,8 @@ pub fn execute_behavior_flee(
     )>,
     others_query: Query<(&Transform, &CurrentTeam, &Health)>,
 ) {
-    let window = window_query.single();
-    query
-        .iter_mut()
-        .for_each(|(current_behavior, _, transform, team, mut velocity)| {
+    query.iter_mut().for_each(
+        |(current_behavior, flee_behavior, transform, team, mut velocity)| {
             if let Behavior::Flee(_) = current_behavior.0 {
                 let enemies_within_range = others_query
                     .iter()
@@ -369,7 +731,7 @@ pub fn execute_behavior_flee(
                             other_team,
                             transform,
                             other_transform,
-                            get_flee_distance(window),
+                            flee_behavior.radius,
                         )
                     })
                     .collect::<Vec<(&Transform, &CurrentTeam, &Health)>>();
@@ -390,76 +752,82 @@ pub fn execute_behavior_flee(
                 let flee_from = center_of_mass.0 / center_of_mass.1;
                 velocity.0 = (transform.translation.truncate() - flee_from).normalize_or_zero();
             };
-        });
+        },
+    );
 }
 
+#[allow(clippy::type_complexity)]
 pub fn execute_behavior_attack(
     time: Res<Time>,
     mut rng: ResMut<RandomSeed>,
     mut query: Query<(
+        Entity,
         &CurrentBehavior,
         &mut AttackBehavior,
+        &Target,
         &Transform,
-        &CurrentTeam,
         &mut Velocity,
+        Option<&AreaAttack>,
     )>,
-    mut others_query: Query<(&Transform, &CurrentTeam, &mut Health)>,
-    mut event_writer: EventWriter<GameEvent>,
+    others_query: Query<(&Transform, &Health)>,
+    mut damage_events: EventWriter<DamageEvent>,
+    mut area_damage_events: EventWriter<AreaDamageEvent>,
 ) {
     query.iter_mut().for_each(
-        |(current_behavior, mut attack_behavior, transform, team, mut velocity)| {
+        |(
+            entity,
+            current_behavior,
+            mut attack_behavior,
+            target,
+            transform,
+            mut velocity,
+            area_attack,
+        )| {
             if let Behavior::Attack(_) = current_behavior.0 {
-                let mut enemies_within_range = others_query
-                    .iter_mut()
-                    .filter(|(other_transform, other_team, other_health)| {
-                        is_other_valid_target(
-                            team,
-                            other_health,
-                            other_team,
-                            transform,
-                            other_transform,
-                            ATTACK_DISTANCE_MAX,
-                        )
-                    })
-                    .collect::<Vec<(&Transform, &CurrentTeam, Mut<Health>)>>();
-
-                enemies_within_range.sort_by(|a, b| {
-                    let distance_to_a =
-                        transform.translation.truncate() - a.0.translation.truncate();
-                    let distance_to_b =
-                        transform.translation.truncate() - b.0.translation.truncate();
-                    distance_to_a
-                        .length()
-                        .partial_cmp(&distance_to_b.length())
-                        .unwrap()
-                });
-
-                if let Some((enemy_transform, enemy_team, enemy_health)) =
-                    enemies_within_range.first_mut()
-                {
+                let Some(target_entity) = target.0 else {
+                    return;
+                };
+
+                if let Ok((enemy_transform, enemy_health)) = others_query.get(target_entity) {
+                    // Someone else might have landed the killing blow earlier this frame
+                    if enemy_health.is_dead() {
+                        return;
+                    }
+
                     let direction =
                         enemy_transform.translation.truncate() - transform.translation.truncate();
 
-                    velocity.0 = if direction.length() > ATTACK_DISTANCE_MID {
+                    // Close in to the engage distance, and stop once well within it
+                    velocity.0 = if direction.length() > attack_behavior.engage_distance {
                         direction.normalize_or_zero()
-                    } else if direction.length() > ATTACK_DISTANCE_MIN {
+                    } else if direction.length()
+                        > attack_behavior.engage_distance * ATTACK_STOP_FRACTION
+                    {
                         velocity.0
                     } else {
                         Vec2::ZERO
                     };
 
                     if attack_behavior.timer.tick(time.delta()).just_finished() {
-                        let final_damage = std::cmp::min(
-                            rng.0.gen_range(
-                                attack_behavior.damage
-                                    ..=attack_behavior.damage
-                                        + attack_behavior.random_attack_offset,
-                            ),
-                            enemy_health.0,
-                        );
-                        enemy_health.0 -= final_damage;
-                        if enemy_health.is_dead() && enemy_team.0 == Team::Good {
-                            event_writer.send(GameEvent::IncreaseScore);
+                        let amount = roll_attack_damage(&mut rng, &attack_behavior);
+                        match area_attack {
+                            Some(area_attack) => {
+                                area_damage_events.send(area_attack.to_event(
+                                    Some(entity),
+                                    transform.translation.truncate(),
+                                    enemy_transform.translation.truncate(),
+                                    amount,
+                                    DamageKind::Physical,
+                                ));
+                            }
+                            None => {
+                                damage_events.send(DamageEvent {
+                                    source: Some(entity),
+                                    target: target_entity,
+                                    amount,
+                                    kind: DamageKind::Physical,
+                                });
+                            }
                         }
 
                         let new_cooldown = attack_behavior.cooldown
@@ -473,6 +841,127 @@ pub fn execute_behavior_attack(
     );
 }
 
+#[allow(clippy::type_complexity)]
+pub fn execute_behavior_ranged_attack(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut rng: ResMut<RandomSeed>,
+    mut query: Query<(
+        Entity,
+        &CurrentBehavior,
+        &RangedAttackBehavior,
+        &mut AttackBehavior,
+        &Target,
+        &Transform,
+        &CurrentTeam,
+        &mut Velocity,
+    )>,
+    others_query: Query<(&Transform, &Health)>,
+) {
+    for (
+        entity,
+        current_behavior,
+        ranged_attack_behavior,
+        mut attack_behavior,
+        target,
+        transform,
+        team,
+        mut velocity,
+    ) in query.iter_mut()
+    {
+        let Behavior::RangedAttack(_) = current_behavior.0 else {
+            continue;
+        };
+
+        let Some((target_entity, (target_transform, target_health))) = target
+            .0
+            .and_then(|target_entity| Some((target_entity, others_query.get(target_entity).ok()?)))
+        else {
+            continue;
+        };
+
+        if target_health.is_dead() {
+            continue;
+        }
+
+        // Keep around the engage distance, backing off when the target gets too close
+        let direction = target_transform.translation.truncate() - transform.translation.truncate();
+        let distance = direction.length();
+        let tolerance = (attack_behavior.range - attack_behavior.engage_distance).abs() * 0.5;
+        velocity.0 = if distance > attack_behavior.engage_distance + tolerance {
+            direction.normalize_or_zero()
+        } else if distance < attack_behavior.engage_distance - tolerance {
+            -direction.normalize_or_zero()
+        } else {
+            Vec2::ZERO
+        };
+
+        if attack_behavior.timer.tick(time.delta()).just_finished() {
+            spawn_projectile(
+                &mut commands,
+                ProjectileLaunch {
+                    source: Some(entity),
+                    team: team.clone(),
+                    target: Some(target_entity),
+                    position: transform.translation.truncate(),
+                    direction,
+                    damage: roll_attack_damage(&mut rng, &attack_behavior),
+                },
+                &ranged_attack_behavior.projectile,
+            );
+
+            let new_cooldown = attack_behavior.cooldown
+                + rand::random::<f32>() * attack_behavior.random_cooldown_offset;
+            attack_behavior.timer = Timer::from_seconds(new_cooldown, TimerMode::Once);
+            attack_behavior.is_attacking = true;
+        }
+    }
+}
+
+pub fn execute_behavior_heal(
+    time: Res<Time>,
+    mut query: Query<(
+        Entity,
+        &CurrentBehavior,
+        &mut HealBehavior,
+        &Transform,
+        &CurrentTeam,
+        &mut Velocity,
+    )>,
+    allies_query: Query<(Entity, &Transform, &CurrentTeam, &Health)>,
+    mut heal_events: EventWriter<HealEvent>,
+) {
+    for (entity, current_behavior, mut heal_behavior, transform, team, mut velocity) in
+        query.iter_mut()
+    {
+        let Behavior::Heal(_) = current_behavior.0 else {
+            continue;
+        };
+
+        let Some((ally, ally_transform)) =
+            get_most_wounded_ally(entity, team, transform, &heal_behavior, allies_query.iter())
+        else {
+            continue;
+        };
+
+        // Walk over to the ally, and keep healing for as long as they stay in range
+        let direction = ally_transform.translation.truncate() - transform.translation.truncate();
+        if direction.length() > heal_behavior.range {
+            velocity.0 = direction.normalize_or_zero();
+            continue;
+        }
+
+        velocity.0 = Vec2::ZERO;
+        if heal_behavior.timer.tick(time.delta()).just_finished() {
+            heal_events.send(HealEvent {
+                source: Some(entity),
+                target: ally,
+                amount: heal_behavior.amount,
+            });
+        }
+    }
+}
+
 pub fn execute_behavior_dead(mut query: Query<(&CurrentBehavior, &DeadBehavior, &mut Velocity)>) {
     for (current_behavior, _, mut velocity) in query.iter_mut() {
         if let Behavior::Dead(_) = current_behavior.0 {
@@ -480,3 +969,36 @@ pub fn execute_behavior_dead(mut query: Query<(&CurrentBehavior, &DeadBehavior,
         }
     }
 }
+
+pub fn execute_behavior_stunned(
+    mut query: Query<(&CurrentBehavior, &mut Velocity, Option<&mut AttackBehavior>)>,
+) {
+    for (current_behavior, mut velocity, attack_behavior) in query.iter_mut() {
+        if let Behavior::Stunned(_) = current_behavior.0 {
+            velocity.0 = Vec2::ZERO;
+            if let Some(mut attack_behavior) = attack_behavior {
+                attack_behavior.is_attacking = false;
+            }
+        }
+    }
+}
+
+pub fn execute_behavior_feared(
+    mut query: Query<(&CurrentBehavior, &StatusEffects, &Transform, &mut Velocity)>,
+    others_query: Query<&Transform>,
+) {
+    for (current_behavior, status_effects, transform, mut velocity) in query.iter_mut() {
+        if let Behavior::Feared(_) = current_behavior.0 {
+            let feared_of = status_effects
+                .get(StatusEffectKind::Fear)
+                .and_then(|fear| fear.source)
+                .and_then(|source| others_query.get(source).ok());
+
+            // Without anyone to run from, keep running the way we already were
+            if let Some(feared_of) = feared_of {
+                velocity.0 = (transform.translation.truncate() - feared_of.translation.truncate())
+                    .normalize_or_zero();
+            }
+        }
+    }
+}
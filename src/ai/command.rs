@@ -0,0 +1,58 @@
+use bevy::prelude::*;
+
+// Orders the player can give to summons, they take precedence over the regular behaviors
+// until they are completed or cancelled
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum UnitCommand {
+    AttackMove(Vec2),   // Move to the point, fighting anything met on the way
+    HoldPosition(Vec2), // Stay at the point, only attacking what comes within reach
+    FollowMe,           // Stop guarding and get back into formation around the player
+    Retreat,            // Disengage and run back to the player, ignoring enemies
+    Rally(Vec2),        // Move to the point without chasing anything, then guard it
+}
+
+impl UnitCommand {
+    pub fn allows_chase(&self) -> bool {
+        matches!(self, UnitCommand::AttackMove(_))
+    }
+
+    pub fn allows_attack(&self) -> bool {
+        !matches!(self, UnitCommand::Retreat)
+    }
+
+    // Attack-moving units are expected to fight far from the point they were sent to
+    pub fn ignores_leash(&self) -> bool {
+        matches!(self, UnitCommand::AttackMove(_))
+    }
+
+    // Hold position never completes, it has to be cancelled or replaced
+    pub fn completes_on_arrival(&self) -> bool {
+        !matches!(self, UnitCommand::HoldPosition(_))
+    }
+
+    pub fn point(&self) -> Option<Vec2> {
+        match self {
+            UnitCommand::AttackMove(point)
+            | UnitCommand::HoldPosition(point)
+            | UnitCommand::Rally(point) => Some(*point),
+            UnitCommand::FollowMe | UnitCommand::Retreat => None,
+        }
+    }
+}
+
+#[derive(Component, Default, Clone, Copy, Debug)]
+pub struct CurrentCommand(pub Option<UnitCommand>);
+
+impl CurrentCommand {
+    pub fn allows_chase(&self) -> bool {
+        self.0.is_none_or(|command| command.allows_chase())
+    }
+
+    pub fn allows_attack(&self) -> bool {
+        self.0.is_none_or(|command| command.allows_attack())
+    }
+
+    pub fn ignores_leash(&self) -> bool {
+        self.0.is_some_and(|command| command.ignores_leash())
+    }
+}
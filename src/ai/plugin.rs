@@ -1,23 +1,70 @@
 use bevy::prelude::*;
 
-use crate::ai::behavior;
+use crate::ai::{ability, behavior, steering, targeting, threat};
+use crate::combat::{area, damage};
+use crate::velocity;
 
 pub struct AiPlugin;
 
 impl Plugin for AiPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(
-            Update,
-            (
-                behavior::behavior_state_machine,
-                behavior::execute_behavior_idle,
-                behavior::execute_behavior_move_origo,
-                behavior::execute_behavior_wander,
-                behavior::execute_behavior_chase,
-                behavior::execute_behavior_flee,
-                behavior::execute_behavior_attack,
-                behavior::execute_behavior_dead,
-            ),
-        );
+        app.add_event::<ability::AbilityEffectEvent>()
+            .add_systems(
+                Update,
+                (
+                    threat::record_threat.before(targeting::update_targets),
+                    threat::decay_threat.before(targeting::update_targets),
+                    threat::update_taunted.before(targeting::update_targets),
+                    targeting::update_targets.before(behavior::behavior_state_machine),
+                    behavior::behavior_state_machine,
+                    behavior::execute_behavior_idle,
+                    behavior::execute_behavior_move_origo,
+                    behavior::execute_behavior_wander,
+                    behavior::update_follow_anchor.before(targeting::update_targets),
+                    behavior::execute_behavior_follow,
+                    behavior::execute_behavior_guard,
+                    behavior::execute_behavior_command,
+                    behavior::execute_behavior_chase,
+                    behavior::execute_behavior_flee,
+                    (
+                        behavior::execute_behavior_attack,
+                        behavior::execute_behavior_ranged_attack,
+                        behavior::execute_behavior_heal.before(damage::resolve_healing),
+                    ),
+                    behavior::execute_behavior_dead,
+                    (
+                        behavior::execute_behavior_stunned,
+                        behavior::execute_behavior_feared,
+                    ),
+                    steering::apply_steering
+                        .after(behavior::execute_behavior_move_origo)
+                        .after(behavior::execute_behavior_chase)
+                        .after(behavior::execute_behavior_flee)
+                        .after(behavior::execute_behavior_follow)
+                        .after(behavior::execute_behavior_guard)
+                        .after(behavior::execute_behavior_command)
+                        .after(behavior::execute_behavior_feared)
+                        .after(behavior::execute_behavior_heal)
+                        .before(velocity::translate),
+                ),
+            )
+            .add_systems(
+                Update,
+                (
+                    ability::tick_ability_cooldowns.before(ability::choose_abilities),
+                    ability::choose_abilities
+                        .after(targeting::update_targets)
+                        .before(behavior::behavior_state_machine),
+                    ability::execute_behavior_casting.after(behavior::behavior_state_machine),
+                    ability::execute_dashing
+                        .after(behavior::behavior_state_machine)
+                        .before(velocity::translate),
+                    ability::resolve_ability_effects
+                        .after(ability::execute_behavior_casting)
+                        .after(ability::execute_dashing)
+                        .before(area::resolve_area_damage)
+                        .before(damage::resolve_damage),
+                ),
+            );
     }
 }
@@ -0,0 +1,172 @@
+use bevy::prelude::*;
+
+use crate::{
+    ai::behavior::{Behavior, CurrentBehavior},
+    collision::{Collider, Obstacle},
+    units::{health::Health, team::CurrentTeam},
+    velocity::Velocity,
+};
+
+// Blends the velocity a behavior asked for with separation, cohesion and obstacle avoidance.
+// The behaviors write their intent to Velocity, and this runs afterwards and adjusts it.
+#[derive(Component, Clone, Debug)]
+pub struct Steering {
+    pub separation_radius: f32,
+    pub separation_weight: f32,
+    pub cohesion_radius: f32,
+    pub cohesion_weight: f32,
+    pub avoidance_distance: f32,
+    pub avoidance_weight: f32,
+}
+
+impl Default for Steering {
+    fn default() -> Self {
+        Steering {
+            separation_radius: 64.0,
+            separation_weight: 1.5,
+            cohesion_radius: 160.0,
+            cohesion_weight: 0.3,
+            avoidance_distance: 96.0,
+            avoidance_weight: 2.0,
+        }
+    }
+}
+
+fn is_steered(behavior: &Behavior) -> bool {
+    matches!(
+        behavior,
+        Behavior::Chase(_)
+            | Behavior::Flee(_)
+            | Behavior::MoveOrigo(_)
+            | Behavior::Follow(_)
+            | Behavior::Guard(_)
+            | Behavior::Command(_)
+            | Behavior::Feared(_)
+    )
+}
+
+fn get_separation(
+    entity: Entity,
+    position: Vec2,
+    steering: &Steering,
+    others_query: &Query<(Entity, &Transform, &CurrentTeam, &Health), With<Velocity>>,
+) -> Vec2 {
+    others_query
+        .iter()
+        .filter(|(other, _, _, other_health)| *other != entity && !other_health.is_dead())
+        .fold(Vec2::ZERO, |acc, (_, other_transform, _, _)| {
+            let offset = position - other_transform.translation.truncate();
+            let distance = offset.length();
+            if distance >= steering.separation_radius || distance <= f32::EPSILON {
+                return acc;
+            }
+
+            // Closer neighbours push harder
+            acc + offset / distance * (1.0 - distance / steering.separation_radius)
+        })
+}
+
+fn get_cohesion(
+    entity: Entity,
+    position: Vec2,
+    team: &CurrentTeam,
+    steering: &Steering,
+    others_query: &Query<(Entity, &Transform, &CurrentTeam, &Health), With<Velocity>>,
+) -> Vec2 {
+    let (sum, count) = others_query
+        .iter()
+        .filter(|(other, other_transform, other_team, other_health)| {
+            *other != entity
+                && team.is_friendly(other_team)
+                && !other_health.is_dead()
+                && (other_transform.translation.truncate() - position).length()
+                    < steering.cohesion_radius
+        })
+        .fold((Vec2::ZERO, 0.0), |acc, (_, other_transform, _, _)| {
+            (acc.0 + other_transform.translation.truncate(), acc.1 + 1.0)
+        });
+
+    if count == 0.0 {
+        return Vec2::ZERO;
+    }
+
+    (sum / count - position).normalize_or_zero()
+}
+
+fn get_avoidance(
+    position: Vec2,
+    direction: Vec2,
+    collider: &Collider,
+    steering: &Steering,
+    obstacle_query: &Query<(&Transform, &Collider), With<Obstacle>>,
+) -> Vec2 {
+    obstacle_query.iter().fold(
+        Vec2::ZERO,
+        |acc, (obstacle_transform, obstacle_collider)| {
+            let to_obstacle = obstacle_transform.translation.truncate() - position;
+            let ahead = to_obstacle.dot(direction);
+            let look_ahead = steering.avoidance_distance + obstacle_collider.radius;
+            if ahead <= 0.0 || ahead > look_ahead {
+                return acc;
+            }
+
+            let clearance = collider.radius + obstacle_collider.radius;
+            let lateral = to_obstacle - direction * ahead;
+            if lateral.length() >= clearance {
+                return acc;
+            }
+
+            // Heading straight into the obstacle, pick a side instead of stopping
+            let away = if lateral.length() > f32::EPSILON {
+                -lateral.normalize()
+            } else {
+                direction.perp()
+            };
+
+            acc + away * (1.0 - ahead / look_ahead)
+        },
+    )
+}
+
+pub fn apply_steering(
+    mut query: Query<(
+        Entity,
+        &CurrentBehavior,
+        &Steering,
+        &Collider,
+        &Transform,
+        &CurrentTeam,
+        &mut Velocity,
+    )>,
+    others_query: Query<(Entity, &Transform, &CurrentTeam, &Health), With<Velocity>>,
+    obstacle_query: Query<(&Transform, &Collider), With<Obstacle>>,
+) {
+    for (entity, current_behavior, steering, collider, transform, team, mut velocity) in
+        query.iter_mut()
+    {
+        // Dead units are never in a steered behavior, so there is no need to check health here
+        if !is_steered(&current_behavior.0) {
+            continue;
+        }
+
+        let intent_speed = velocity.0.length();
+        if intent_speed <= f32::EPSILON {
+            continue;
+        }
+
+        let position = transform.translation.truncate();
+        let direction = velocity.0 / intent_speed;
+
+        let separation = get_separation(entity, position, steering, &others_query);
+        let cohesion = get_cohesion(entity, position, team, steering, &others_query);
+        let avoidance = get_avoidance(position, direction, collider, steering, &obstacle_query);
+
+        let steered = direction
+            + separation * steering.separation_weight
+            + cohesion * steering.cohesion_weight
+            + avoidance * steering.avoidance_weight;
+
+        // Steering only changes the heading, the behavior still decides how fast to go
+        velocity.0 = steered.normalize_or_zero() * intent_speed;
+    }
+}
@@ -0,0 +1,278 @@
+use bevy::prelude::*;
+
+use crate::{
+    ai::{
+        behavior::{is_other_valid_target, AttackBehavior, ChaseBehavior, FollowBehavior},
+        command::CurrentCommand,
+        threat::{Taunted, ThreatTable},
+    },
+    player::plugin::Player,
+    structures::{altar::Altar, structure::StructureType},
+    units::{health::Health, team::CurrentTeam, unit_types::Acolyte},
+};
+
+// The entity a unit is focusing, it is kept until it dies or leaves chase range
+#[derive(Component, Default, Clone, Copy, Debug)]
+pub struct Target(pub Option<Entity>);
+
+#[derive(Component, Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TargetSelection {
+    #[default]
+    Nearest,
+    LowestHealth,
+    HighestThreat,
+    PreferAcolytes,
+    PreferPlayer,
+    PreferStructures, // Structures and acolytes, what keeps the player's side going
+}
+
+struct TargetCandidate {
+    entity: Entity,
+    distance: f32,
+    health: u32,
+    threat: f32,
+    estimated_threat: f32,
+    is_acolyte: bool,
+    is_player: bool,
+    is_structure: bool,
+}
+
+// A new target has to be this much more threatening than the current one to steal aggro
+const THREAT_SWITCH_RATIO: f32 = 1.1;
+
+// Without any other information, a unit is as threatening as the damage it can deal per second
+fn get_estimated_threat(attack_behavior: Option<&AttackBehavior>) -> f32 {
+    match attack_behavior {
+        Some(attack_behavior) => attack_behavior.damage as f32 / attack_behavior.cooldown,
+        None => 0.0,
+    }
+}
+
+fn get_nearest<'a>(candidates: impl Iterator<Item = &'a TargetCandidate>) -> Option<Entity> {
+    candidates
+        .min_by(|a, b| a.distance.total_cmp(&b.distance))
+        .map(|candidate| candidate.entity)
+}
+
+fn get_nearest_preferred(
+    candidates: &[TargetCandidate],
+    is_preferred: impl Fn(&TargetCandidate) -> bool,
+) -> Option<Entity> {
+    get_nearest(
+        candidates
+            .iter()
+            .filter(|candidate| is_preferred(candidate)),
+    )
+    .or_else(|| get_nearest(candidates.iter()))
+}
+
+fn select_target(selection: TargetSelection, candidates: &[TargetCandidate]) -> Option<Entity> {
+    match selection {
+        TargetSelection::Nearest => get_nearest(candidates.iter()),
+        TargetSelection::LowestHealth => candidates
+            .iter()
+            .min_by(|a, b| {
+                a.health
+                    .cmp(&b.health)
+                    .then(a.distance.total_cmp(&b.distance))
+            })
+            .map(|candidate| candidate.entity),
+        TargetSelection::HighestThreat => candidates
+            .iter()
+            .max_by(|a, b| {
+                a.threat
+                    .total_cmp(&b.threat)
+                    .then(a.estimated_threat.total_cmp(&b.estimated_threat))
+                    .then(b.distance.total_cmp(&a.distance))
+            })
+            .map(|candidate| candidate.entity),
+        TargetSelection::PreferAcolytes => {
+            get_nearest_preferred(candidates, |candidate| candidate.is_acolyte)
+        }
+        TargetSelection::PreferPlayer => {
+            get_nearest_preferred(candidates, |candidate| candidate.is_player)
+        }
+        TargetSelection::PreferStructures => get_nearest_preferred(candidates, |candidate| {
+            candidate.is_structure || candidate.is_acolyte
+        }),
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn update_targets(
+    mut query: Query<(
+        &mut Target,
+        &TargetSelection,
+        &Transform,
+        &CurrentTeam,
+        &Health,
+        Option<&ThreatTable>,
+        Option<&Taunted>,
+        Option<&FollowBehavior>,
+        Option<&ChaseBehavior>,
+        Option<&CurrentCommand>,
+        Option<&AttackBehavior>,
+    )>,
+    others_query: Query<(
+        Entity,
+        &Transform,
+        &CurrentTeam,
+        &Health,
+        Option<&AttackBehavior>,
+        Has<Acolyte>,
+        Has<Player>,
+        Has<StructureType>,
+        Has<Altar>,
+    )>,
+) {
+    for (
+        mut target,
+        selection,
+        transform,
+        team,
+        health,
+        threat_table,
+        taunted,
+        follow_behavior,
+        chase_behavior,
+        current_command,
+        attack_behavior,
+    ) in query.iter_mut()
+    {
+        let chase_distance = chase_behavior.copied().unwrap_or_default().radius;
+
+        if health.is_dead() || follow_behavior.is_some_and(|follow| follow.is_returning) {
+            target.0 = None;
+            continue;
+        }
+
+        if let Some(taunted) = taunted {
+            target.0 = Some(taunted.taunter);
+            continue;
+        }
+
+        // Units that follow an anchor only pick fights they can finish without breaking the leash
+        let ignores_leash = current_command.is_some_and(|command| command.ignores_leash());
+        let is_within_leash = |other_transform: &Transform| {
+            ignores_leash
+                || follow_behavior.is_none_or(|follow| {
+                    (other_transform.translation.truncate() - follow.anchor).length()
+                        < follow.leash_distance
+                })
+        };
+
+        let get_threat =
+            |entity: Entity| threat_table.map_or(0.0, |threat_table| threat_table.get(entity));
+
+        let is_current_target_valid = target.0.is_some_and(|entity| {
+            others_query.get(entity).is_ok_and(
+                |(_, other_transform, other_team, other_health, _, _, _, _, _)| {
+                    is_within_leash(other_transform)
+                        && is_other_valid_target(
+                            team,
+                            other_health,
+                            other_team,
+                            transform,
+                            other_transform,
+                            chase_distance,
+                        )
+                },
+            )
+        });
+
+        // Enemies within attack range come first, so a unit on its way to a distant target
+        // doesn't let someone beat on it unanswered
+        let is_in_reach = |distance: f32| {
+            attack_behavior.is_none_or(|attack_behavior| distance < attack_behavior.range)
+        };
+        let is_current_target_in_reach = target
+            .0
+            .and_then(|entity| others_query.get(entity).ok())
+            .is_some_and(|(_, other_transform, _, _, _, _, _, _, _)| {
+                is_in_reach(
+                    (other_transform.translation.truncate() - transform.translation.truncate())
+                        .length(),
+                )
+            });
+
+        // Only threat based units reconsider a valid target within reach, and only when someone
+        // clearly out-threatens it
+        if is_current_target_valid
+            && is_current_target_in_reach
+            && *selection != TargetSelection::HighestThreat
+        {
+            continue;
+        }
+
+        let candidates = others_query
+            .iter()
+            .filter(
+                |(_, other_transform, other_team, other_health, _, _, _, _, _)| {
+                    is_within_leash(other_transform)
+                        && is_other_valid_target(
+                            team,
+                            other_health,
+                            other_team,
+                            transform,
+                            other_transform,
+                            chase_distance,
+                        )
+                },
+            )
+            .map(
+                |(
+                    entity,
+                    other_transform,
+                    _,
+                    other_health,
+                    attack_behavior,
+                    is_acolyte,
+                    is_player,
+                    is_structure,
+                    is_altar,
+                )| {
+                    TargetCandidate {
+                        entity,
+                        distance: (other_transform.translation.truncate()
+                            - transform.translation.truncate())
+                        .length(),
+                        health: other_health.current,
+                        threat: get_threat(entity),
+                        estimated_threat: get_estimated_threat(attack_behavior),
+                        is_acolyte,
+                        is_player,
+                        is_structure: is_structure || is_altar,
+                    }
+                },
+            )
+            .collect::<Vec<TargetCandidate>>();
+
+        let (in_reach, out_of_reach): (Vec<TargetCandidate>, Vec<TargetCandidate>) = candidates
+            .into_iter()
+            .partition(|candidate| attack_behavior.is_some() && is_in_reach(candidate.distance));
+        if is_current_target_valid && !is_current_target_in_reach && !in_reach.is_empty() {
+            target.0 = select_target(*selection, &in_reach);
+            continue;
+        }
+
+        let candidates = if in_reach.is_empty() {
+            out_of_reach
+        } else {
+            in_reach
+        };
+
+        if is_current_target_valid {
+            let current_threat = target.0.map_or(0.0, get_threat);
+            let new_target = select_target(*selection, &candidates);
+            if let Some(new_target) = new_target {
+                if get_threat(new_target) > current_threat * THREAT_SWITCH_RATIO {
+                    target.0 = Some(new_target);
+                }
+            }
+
+            continue;
+        }
+
+        target.0 = select_target(*selection, &candidates);
+    }
+}
@@ -0,0 +1,79 @@
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+use crate::{combat::damage::UnitDamaged, units::health::Health};
+
+// How much of the accumulated threat is left after one second
+const THREAT_DECAY_PER_SECOND: f32 = 0.9;
+const THREAT_FORGET_THRESHOLD: f32 = 0.5;
+
+// Who has damaged this unit, and by how much
+#[derive(Component, Default, Clone, Debug)]
+pub struct ThreatTable(pub HashMap<Entity, f32>);
+
+impl ThreatTable {
+    pub fn add(&mut self, source: Entity, amount: f32) {
+        *self.0.entry(source).or_insert(0.0) += amount;
+    }
+
+    pub fn get(&self, source: Entity) -> f32 {
+        self.0.get(&source).copied().unwrap_or(0.0)
+    }
+}
+
+// Forces the unit to target the taunter until the timer runs out
+#[derive(Component, Clone, Debug)]
+pub struct Taunted {
+    pub taunter: Entity,
+    pub timer: Timer,
+}
+
+pub fn record_threat(
+    mut damaged_events: EventReader<UnitDamaged>,
+    mut query: Query<&mut ThreatTable>,
+) {
+    for event in damaged_events.read() {
+        let Some(source) = event.source else {
+            continue;
+        };
+
+        if let Ok(mut threat_table) = query.get_mut(event.target) {
+            threat_table.add(source, event.amount as f32);
+        }
+    }
+}
+
+pub fn decay_threat(
+    time: Res<Time>,
+    mut query: Query<&mut ThreatTable>,
+    health_query: Query<&Health>,
+) {
+    let decay = THREAT_DECAY_PER_SECOND.powf(time.delta_seconds());
+    for mut threat_table in query.iter_mut() {
+        threat_table.0.retain(|source, threat| {
+            *threat *= decay;
+            let is_source_alive = health_query
+                .get(*source)
+                .is_ok_and(|health| !health.is_dead());
+
+            is_source_alive && *threat > THREAT_FORGET_THRESHOLD
+        });
+    }
+}
+
+pub fn update_taunted(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut Taunted, &Health)>,
+    taunter_query: Query<&Health>,
+) {
+    for (entity, mut taunted, health) in query.iter_mut() {
+        let is_taunter_alive = taunter_query
+            .get(taunted.taunter)
+            .is_ok_and(|taunter_health| !taunter_health.is_dead());
+
+        if health.is_dead() || !is_taunter_alive || taunted.timer.tick(time.delta()).finished() {
+            commands.entity(entity).remove::<Taunted>();
+        }
+    }
+}
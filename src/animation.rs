@@ -19,6 +19,10 @@ pub struct Animation {
     pub frame_timer: Timer,
 }
 
+// The color the unit's sprites return to when nothing else is tinting them
+#[derive(Component, Debug, Clone, Copy, Default)]
+pub struct BaseTint(pub Color);
+
 #[derive(Component, Debug, Clone, PartialEq, Eq, Default)]
 pub struct CurrentAnimation {
     pub animation_type: AnimationType,
@@ -0,0 +1,83 @@
+use bevy::prelude::*;
+
+use crate::units::health::Health;
+
+#[derive(Component, Clone, Copy, Debug)]
+pub struct Collider {
+    pub radius: f32,
+}
+
+impl Default for Collider {
+    fn default() -> Self {
+        Collider { radius: 20.0 }
+    }
+}
+
+// Static colliders that units steer around and can never be pushed into
+#[derive(Component, Default)]
+pub struct Obstacle;
+
+// Never pushed around by units, but doesn't stop anyone from walking over it either
+#[derive(Component, Default)]
+pub struct Anchored;
+
+fn get_push_out(position: Vec2, other_position: Vec2, min_distance: f32) -> Option<Vec2> {
+    let offset = position - other_position;
+    let distance = offset.length();
+    if distance >= min_distance {
+        return None;
+    }
+
+    // Units spawned on the exact same spot have no direction to separate in, pick one
+    let direction = if distance > f32::EPSILON {
+        offset / distance
+    } else {
+        Vec2::X
+    };
+
+    Some(direction * (min_distance - distance))
+}
+
+#[allow(clippy::type_complexity)]
+pub fn resolve_collisions(
+    mut query: Query<(&Collider, &Health, &mut Transform), (Without<Obstacle>, Without<Anchored>)>,
+    obstacle_query: Query<(&Collider, &Transform), With<Obstacle>>,
+) {
+    let mut combinations = query.iter_combinations_mut();
+    while let Some(
+        [(collider, health, mut transform), (other_collider, other_health, mut other_transform)],
+    ) = combinations.fetch_next()
+    {
+        // Corpses are allowed to pile up
+        if health.is_dead() || other_health.is_dead() {
+            continue;
+        }
+
+        if let Some(push) = get_push_out(
+            transform.translation.truncate(),
+            other_transform.translation.truncate(),
+            collider.radius + other_collider.radius,
+        ) {
+            // Both units share the correction so neither is favored
+            let half_push = (push * 0.5).extend(0.0);
+            transform.translation += half_push;
+            other_transform.translation -= half_push;
+        }
+    }
+
+    for (collider, health, mut transform) in query.iter_mut() {
+        if health.is_dead() {
+            continue;
+        }
+
+        for (obstacle_collider, obstacle_transform) in obstacle_query.iter() {
+            if let Some(push) = get_push_out(
+                transform.translation.truncate(),
+                obstacle_transform.translation.truncate(),
+                collider.radius + obstacle_collider.radius,
+            ) {
+                transform.translation += push.extend(0.0);
+            }
+        }
+    }
+}
@@ -0,0 +1,160 @@
+use bevy::prelude::*;
+
+use crate::{
+    collision::Collider,
+    combat::damage::{DamageEvent, DamageKind},
+    gamestate::GameMode,
+    units::{health::Health, team::CurrentTeam},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AreaShape {
+    Circle { radius: f32 },
+    Cone { radius: f32, angle: f32 }, // The angle is the full width of the cone, in radians
+    Line { length: f32, width: f32 },
+}
+
+// Where the shape is placed, it always points from the attacker towards the target
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AreaOrigin {
+    #[default]
+    Target,
+    Attacker,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FriendlyFire {
+    #[default]
+    FromGameMode,
+    Enabled,
+    Disabled,
+}
+
+// Replaces the single target hit of a unit's attack with everything inside the shape
+#[derive(Component, Debug, Clone, Copy)]
+pub struct AreaAttack {
+    pub shape: AreaShape,
+    pub origin: AreaOrigin,
+    pub falloff: f32, // Fraction of the damage lost at the edge of the shape
+    pub friendly_fire: FriendlyFire,
+}
+
+#[derive(Event, Debug, Clone, Copy)]
+pub struct AreaDamageEvent {
+    pub source: Option<Entity>,
+    pub center: Vec2,
+    pub direction: Vec2,
+    pub area: AreaAttack,
+    pub amount: u32,
+    pub kind: DamageKind,
+}
+
+impl AreaAttack {
+    pub fn to_event(
+        &self,
+        source: Option<Entity>,
+        attacker_position: Vec2,
+        target_position: Vec2,
+        amount: u32,
+        kind: DamageKind,
+    ) -> AreaDamageEvent {
+        let center = match self.origin {
+            AreaOrigin::Target => target_position,
+            AreaOrigin::Attacker => attacker_position,
+        };
+
+        AreaDamageEvent {
+            source,
+            center,
+            direction: (target_position - attacker_position)
+                .try_normalize()
+                .unwrap_or(Vec2::X),
+            area: *self,
+            amount,
+            kind,
+        }
+    }
+}
+
+// How far into the shape the position is, from 0.0 at the center to 1.0 at the edge, if it's inside
+pub fn get_distance_into_shape(
+    shape: AreaShape,
+    center: Vec2,
+    direction: Vec2,
+    position: Vec2,
+    radius: f32,
+) -> Option<f32> {
+    let offset = position - center;
+    match shape {
+        AreaShape::Circle {
+            radius: shape_radius,
+        } => {
+            let distance = (offset.length() - radius).max(0.0);
+            (distance <= shape_radius).then(|| distance / shape_radius)
+        }
+        AreaShape::Cone {
+            radius: shape_radius,
+            angle,
+        } => {
+            let distance = (offset.length() - radius).max(0.0);
+            let is_within_angle =
+                offset.length() <= radius || direction.angle_between(offset).abs() <= angle * 0.5;
+            (distance <= shape_radius && is_within_angle).then(|| distance / shape_radius)
+        }
+        AreaShape::Line { length, width } => {
+            let along = offset.dot(direction);
+            let across = offset.perp_dot(direction).abs();
+            let is_inside =
+                along >= -radius && along <= length + radius && across <= width * 0.5 + radius;
+            is_inside.then(|| along.clamp(0.0, length) / length)
+        }
+    }
+}
+
+pub fn resolve_area_damage(
+    game_mode: Res<GameMode>,
+    mut area_damage_events: EventReader<AreaDamageEvent>,
+    mut damage_events: EventWriter<DamageEvent>,
+    query: Query<(Entity, &Transform, &Collider, &CurrentTeam, &Health)>,
+    source_query: Query<&CurrentTeam>,
+) {
+    for event in area_damage_events.read() {
+        let source_team = event
+            .source
+            .and_then(|source| source_query.get(source).ok());
+        let allows_friendly_fire = match event.area.friendly_fire {
+            FriendlyFire::FromGameMode => game_mode.friendly_fire,
+            FriendlyFire::Enabled => true,
+            FriendlyFire::Disabled => false,
+        };
+
+        for (entity, transform, collider, team, health) in query.iter() {
+            if health.is_dead() || event.source == Some(entity) {
+                continue;
+            }
+
+            let is_friendly = source_team.is_some_and(|source_team| source_team.is_friendly(team));
+            if is_friendly && !allows_friendly_fire {
+                continue;
+            }
+
+            let Some(distance_into_shape) = get_distance_into_shape(
+                event.area.shape,
+                event.center,
+                event.direction,
+                transform.translation.truncate(),
+                collider.radius,
+            ) else {
+                continue;
+            };
+
+            let falloff = 1.0 - event.area.falloff.clamp(0.0, 1.0) * distance_into_shape;
+            damage_events.send(DamageEvent {
+                source: event.source,
+                target: entity,
+                amount: (event.amount as f32 * falloff).round() as u32,
+                kind: event.kind,
+            });
+        }
+    }
+}
@@ -0,0 +1,252 @@
+use bevy::prelude::*;
+use rand::Rng;
+use std::collections::HashMap;
+
+use crate::{ai::behavior::AttackBehavior, dark_arts_defense::RandomSeed, units::health::Health};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum DamageKind {
+    #[default]
+    Physical,
+    Dark,
+    Holy,
+    Fire,
+    Poison,
+    True, // Ignores armor, resistances and crits
+}
+
+// Something wants to hurt something else, nothing has been applied yet
+#[derive(Event, Clone, Copy, Debug)]
+pub struct DamageEvent {
+    pub source: Option<Entity>,
+    pub target: Entity,
+    pub amount: u32,
+    pub kind: DamageKind,
+}
+
+// Sent after the damage has been applied to the target's health
+#[derive(Event, Clone, Copy, Debug)]
+pub struct UnitDamaged {
+    pub source: Option<Entity>,
+    pub target: Entity,
+    pub amount: u32,
+    pub kind: DamageKind,
+    pub is_critical: bool,
+}
+
+#[derive(Event, Clone, Copy, Debug)]
+pub struct HealEvent {
+    pub source: Option<Entity>,
+    pub target: Entity,
+    pub amount: u32,
+}
+
+// Sent after the health has been restored, the amount is what was actually healed
+#[derive(Event, Clone, Copy, Debug)]
+pub struct UnitHealed {
+    pub source: Option<Entity>,
+    pub target: Entity,
+    pub amount: u32,
+}
+
+#[derive(Event, Clone, Copy, Debug)]
+pub struct UnitDied {
+    pub source: Option<Entity>,
+    pub target: Entity,
+}
+
+// Flat reduction of physical damage
+#[derive(Component, Default, Clone, Copy, Debug)]
+pub struct Armor(pub u32);
+
+// Fraction of the damage of each kind that is ignored, 1.0 is immune
+#[derive(Component, Default, Clone, Debug)]
+pub struct Resistances(pub HashMap<DamageKind, f32>);
+
+#[derive(Component, Clone, Copy, Debug)]
+pub struct CriticalStrike {
+    pub chance: f32,
+    pub multiplier: f32,
+}
+
+impl Default for CriticalStrike {
+    fn default() -> Self {
+        CriticalStrike {
+            chance: 0.0,
+            multiplier: 2.0,
+        }
+    }
+}
+
+#[derive(Component, Clone, Debug)]
+pub struct Invulnerable {
+    pub timer: Timer,
+}
+
+impl Invulnerable {
+    pub fn from_seconds(duration: f32) -> Self {
+        Invulnerable {
+            timer: Timer::from_seconds(duration, TimerMode::Once),
+        }
+    }
+}
+
+// Soaks up damage before it reaches the health, gone when used up or when the timer runs out
+#[derive(Component, Clone, Debug)]
+pub struct Shield {
+    pub amount: u32,
+    pub timer: Timer,
+}
+
+impl Shield {
+    pub fn new(amount: u32, duration: f32) -> Self {
+        Shield {
+            amount,
+            timer: Timer::from_seconds(duration, TimerMode::Once),
+        }
+    }
+}
+
+pub fn roll_attack_damage(rng: &mut RandomSeed, attack_behavior: &AttackBehavior) -> u32 {
+    rng.0.gen_range(
+        attack_behavior.damage
+            ..=attack_behavior
+                .damage
+                .saturating_add(attack_behavior.random_attack_offset),
+    )
+}
+
+#[allow(clippy::type_complexity)]
+pub fn resolve_damage(
+    mut commands: Commands,
+    mut rng: ResMut<RandomSeed>,
+    mut damage_events: EventReader<DamageEvent>,
+    mut damaged_events: EventWriter<UnitDamaged>,
+    mut died_events: EventWriter<UnitDied>,
+    mut target_query: Query<(
+        &mut Health,
+        Option<&Armor>,
+        Option<&Resistances>,
+        Option<&mut Shield>,
+        Has<Invulnerable>,
+    )>,
+    source_query: Query<&CriticalStrike>,
+) {
+    for event in damage_events.read() {
+        let Ok((mut health, armor, resistances, shield, is_invulnerable)) =
+            target_query.get_mut(event.target)
+        else {
+            continue;
+        };
+
+        if health.is_dead() || is_invulnerable {
+            continue;
+        }
+
+        let mut amount = event.amount as f32;
+        let mut is_critical = false;
+        if event.kind != DamageKind::True {
+            if event.kind == DamageKind::Physical {
+                amount -= armor.map_or(0.0, |armor| armor.0 as f32);
+            }
+
+            let resistance = resistances
+                .and_then(|resistances| resistances.0.get(&event.kind).copied())
+                .unwrap_or(0.0);
+            amount *= 1.0 - resistance.clamp(0.0, 1.0);
+
+            let critical_strike = event
+                .source
+                .and_then(|source| source_query.get(source).ok());
+            if let Some(critical_strike) = critical_strike {
+                if rng.0.gen::<f32>() < critical_strike.chance {
+                    amount *= critical_strike.multiplier;
+                    is_critical = true;
+                }
+            }
+        }
+
+        // A hit always does at least a little damage, no matter the armor
+        let mut final_damage = amount.round().max(1.0) as u32;
+        if let Some(mut shield) = shield {
+            let absorbed = final_damage.min(shield.amount);
+            shield.amount -= absorbed;
+            final_damage -= absorbed;
+            if shield.amount == 0 {
+                commands.entity(event.target).remove::<Shield>();
+            }
+
+            if final_damage == 0 {
+                continue;
+            }
+        }
+
+        let final_damage = final_damage.min(health.current);
+        health.current -= final_damage;
+
+        damaged_events.send(UnitDamaged {
+            source: event.source,
+            target: event.target,
+            amount: final_damage,
+            kind: event.kind,
+            is_critical,
+        });
+
+        if health.is_dead() {
+            died_events.send(UnitDied {
+                source: event.source,
+                target: event.target,
+            });
+        }
+    }
+}
+
+pub fn resolve_healing(
+    mut heal_events: EventReader<HealEvent>,
+    mut healed_events: EventWriter<UnitHealed>,
+    mut query: Query<&mut Health>,
+) {
+    for event in heal_events.read() {
+        let Ok(mut health) = query.get_mut(event.target) else {
+            continue;
+        };
+
+        // The dead stay dead, and nobody is healed past their max
+        if health.is_dead() || health.current >= health.max {
+            continue;
+        }
+
+        let healed = event.amount.min(health.max - health.current);
+        health.current += healed;
+
+        healed_events.send(UnitHealed {
+            source: event.source,
+            target: event.target,
+            amount: healed,
+        });
+    }
+}
+
+pub fn update_invulnerable(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut Invulnerable)>,
+) {
+    for (entity, mut invulnerable) in query.iter_mut() {
+        if invulnerable.timer.tick(time.delta()).finished() {
+            commands.entity(entity).remove::<Invulnerable>();
+        }
+    }
+}
+
+pub fn update_shields(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut Shield)>,
+) {
+    for (entity, mut shield) in query.iter_mut() {
+        if shield.timer.tick(time.delta()).finished() {
+            commands.entity(entity).remove::<Shield>();
+        }
+    }
+}
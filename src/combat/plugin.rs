@@ -0,0 +1,41 @@
+use bevy::prelude::*;
+
+use crate::ai::behavior;
+use crate::combat::area::{self, AreaDamageEvent};
+use crate::combat::damage::{self, DamageEvent, HealEvent, UnitDamaged, UnitDied, UnitHealed};
+use crate::combat::projectile;
+use crate::combat::status::{self, ApplyStatusEffect};
+
+pub struct CombatPlugin;
+
+impl Plugin for CombatPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<DamageEvent>()
+            .add_event::<UnitDamaged>()
+            .add_event::<UnitDied>()
+            .add_event::<HealEvent>()
+            .add_event::<UnitHealed>()
+            .add_event::<ApplyStatusEffect>()
+            .add_event::<AreaDamageEvent>()
+            .add_systems(
+                Update,
+                (
+                    damage::resolve_damage.after(behavior::execute_behavior_attack),
+                    damage::resolve_healing.after(damage::resolve_damage),
+                    damage::update_invulnerable,
+                    damage::update_shields,
+                    area::resolve_area_damage
+                        .after(behavior::execute_behavior_attack)
+                        .before(damage::resolve_damage),
+                    projectile::move_projectiles,
+                    projectile::projectile_hits
+                        .after(projectile::move_projectiles)
+                        .before(damage::resolve_damage),
+                    status::apply_on_hit_status_effects.after(damage::resolve_damage),
+                    status::apply_status_effects.after(status::apply_on_hit_status_effects),
+                    status::update_status_effects.before(damage::resolve_damage),
+                    status::update_status_tints.after(status::update_status_effects),
+                ),
+            );
+    }
+}
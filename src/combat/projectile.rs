@@ -0,0 +1,178 @@
+use bevy::prelude::*;
+
+use crate::{
+    collision::Collider,
+    combat::damage::{DamageEvent, DamageKind},
+    gamestate::Cleanup,
+    units::{health::Health, team::CurrentTeam},
+};
+
+// Everything needed to fire a projectile, shared by everyone shooting the same kind of projectile
+#[derive(Clone, Debug)]
+pub struct ProjectileParams {
+    pub speed: f32,
+    pub hit_radius: f32,
+    pub lifetime: f32,
+    pub kind: DamageKind,
+    pub is_homing: bool,
+    pub turn_rate: f32, // Radians per second a homing projectile can turn
+    pub pierce: u32,    // How many units the projectile passes through before it is used up
+    pub size: Vec2,
+    pub color: Color,
+}
+
+impl Default for ProjectileParams {
+    fn default() -> Self {
+        ProjectileParams {
+            speed: 500.0,
+            hit_radius: 8.0,
+            lifetime: 2.0,
+            kind: DamageKind::Physical,
+            is_homing: false,
+            turn_rate: 4.0,
+            pierce: 0,
+            size: Vec2::new(16.0, 4.0),
+            color: Color::WHITE,
+        }
+    }
+}
+
+#[derive(Component, Clone, Debug)]
+pub struct Projectile {
+    pub source: Option<Entity>,
+    pub team: CurrentTeam,
+    pub target: Option<Entity>,
+    pub direction: Vec2,
+    pub damage: u32,
+    pub params: ProjectileParams,
+    pub pierce_left: u32,
+    pub hit_entities: Vec<Entity>,
+    pub lifetime_timer: Timer,
+}
+
+// Who fires a single projectile, where from and at what
+#[derive(Clone, Debug)]
+pub struct ProjectileLaunch {
+    pub source: Option<Entity>,
+    pub team: CurrentTeam,
+    pub target: Option<Entity>,
+    pub position: Vec2,
+    pub direction: Vec2,
+    pub damage: u32,
+}
+
+pub fn spawn_projectile(
+    commands: &mut Commands,
+    launch: ProjectileLaunch,
+    params: &ProjectileParams,
+) {
+    let ProjectileLaunch {
+        source,
+        team,
+        target,
+        position,
+        direction,
+        damage,
+    } = launch;
+    let direction = direction.normalize_or_zero();
+    commands.spawn((
+        SpriteBundle {
+            sprite: Sprite {
+                color: params.color,
+                custom_size: Some(params.size),
+                ..default()
+            },
+            transform: Transform::from_translation(position.extend(15.0))
+                .with_rotation(Quat::from_rotation_z(direction.to_angle())),
+            ..default()
+        },
+        Projectile {
+            source,
+            team,
+            target,
+            direction,
+            damage,
+            params: params.clone(),
+            pierce_left: params.pierce,
+            hit_entities: Vec::new(),
+            lifetime_timer: Timer::from_seconds(params.lifetime, TimerMode::Once),
+        },
+        Cleanup,
+    ));
+}
+
+pub fn move_projectiles(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut Projectile, &mut Transform)>,
+    target_query: Query<(&Transform, &Health), Without<Projectile>>,
+) {
+    for (entity, mut projectile, mut transform) in query.iter_mut() {
+        if projectile.lifetime_timer.tick(time.delta()).finished() {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+
+        let position = transform.translation.truncate();
+        let homing_target = projectile
+            .target
+            .filter(|_| projectile.params.is_homing)
+            .and_then(|target| target_query.get(target).ok())
+            .filter(|(_, health)| !health.is_dead());
+
+        // Turn towards the target, but only as fast as the turn rate allows so it can still miss
+        if let Some((target_transform, _)) = homing_target {
+            let wanted_direction =
+                (target_transform.translation.truncate() - position).normalize_or_zero();
+            let angle = projectile.direction.angle_between(wanted_direction);
+            if wanted_direction != Vec2::ZERO && angle.is_finite() {
+                let max_turn = projectile.params.turn_rate * time.delta_seconds();
+                let turn = angle.clamp(-max_turn, max_turn);
+                projectile.direction = Vec2::from_angle(turn).rotate(projectile.direction);
+            }
+        }
+
+        let movement = projectile.direction * projectile.params.speed * time.delta_seconds();
+        transform.translation += movement.extend(0.0);
+        transform.rotation = Quat::from_rotation_z(projectile.direction.to_angle());
+    }
+}
+
+pub fn projectile_hits(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut Projectile, &Transform)>,
+    units_query: Query<(Entity, &Transform, &Collider, &CurrentTeam, &Health), Without<Projectile>>,
+    mut damage_events: EventWriter<DamageEvent>,
+) {
+    for (entity, mut projectile, transform) in query.iter_mut() {
+        let position = transform.translation.truncate();
+        for (unit, unit_transform, collider, team, health) in units_query.iter() {
+            if health.is_dead()
+                || projectile.team.is_friendly(team)
+                || projectile.hit_entities.contains(&unit)
+            {
+                continue;
+            }
+
+            let distance = (unit_transform.translation.truncate() - position).length();
+            if distance > collider.radius + projectile.params.hit_radius {
+                continue;
+            }
+
+            damage_events.send(DamageEvent {
+                source: projectile.source,
+                target: unit,
+                amount: projectile.damage,
+                kind: projectile.params.kind,
+            });
+            projectile.hit_entities.push(unit);
+
+            if projectile.pierce_left == 0 {
+                commands.entity(entity).despawn_recursive();
+                break;
+            }
+
+            projectile.pierce_left -= 1;
+        }
+    }
+}
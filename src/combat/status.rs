@@ -0,0 +1,290 @@
+use bevy::prelude::*;
+
+use crate::{
+    animation::{Animation, BaseTint},
+    combat::damage::{DamageEvent, DamageKind, Invulnerable, UnitDamaged},
+    units::{
+        health::Health,
+        stats::{ModifierKind, Stat, StatModifier, StatModifiers},
+    },
+};
+
+const SLOW_MODIFIER_SOURCE: &str = "slow";
+const DAMAGE_OVER_TIME_TICK: f32 = 1.0;
+const INVULNERABLE_ALPHA: f32 = 0.4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StatusEffectKind {
+    Slow,   // Magnitude is the fraction of speed that is lost
+    Stun,   // Can't move or act
+    Poison, // Magnitude is the damage per tick and stack
+    Burn,   // Magnitude is the damage per tick
+    Fear,   // Runs away from whoever applied it
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StackingRule {
+    Refresh,                   // Keep the strongest magnitude and the longest duration
+    Stack { max_stacks: u32 }, // Every application adds a stack and refreshes the duration
+}
+
+impl StatusEffectKind {
+    pub fn stacking_rule(&self) -> StackingRule {
+        match self {
+            StatusEffectKind::Poison => StackingRule::Stack { max_stacks: 5 },
+            _ => StackingRule::Refresh,
+        }
+    }
+
+    pub fn damage_kind(&self) -> Option<DamageKind> {
+        match self {
+            StatusEffectKind::Poison => Some(DamageKind::Poison),
+            StatusEffectKind::Burn => Some(DamageKind::Fire),
+            _ => None,
+        }
+    }
+
+    pub fn tint(&self) -> Color {
+        match self {
+            StatusEffectKind::Slow => Color::rgb(0.5, 0.7, 1.0),
+            StatusEffectKind::Stun => Color::rgb(1.0, 1.0, 0.4),
+            StatusEffectKind::Poison => Color::rgb(0.5, 1.0, 0.4),
+            StatusEffectKind::Burn => Color::rgb(1.0, 0.5, 0.2),
+            StatusEffectKind::Fear => Color::rgb(0.7, 0.3, 0.9),
+        }
+    }
+}
+
+// The order the tints are picked in when a unit suffers from several effects at once
+const TINT_PRIORITY: [StatusEffectKind; 5] = [
+    StatusEffectKind::Stun,
+    StatusEffectKind::Fear,
+    StatusEffectKind::Burn,
+    StatusEffectKind::Poison,
+    StatusEffectKind::Slow,
+];
+
+#[derive(Debug, Clone)]
+pub struct StatusEffect {
+    pub kind: StatusEffectKind,
+    pub magnitude: f32,
+    pub stacks: u32,
+    pub source: Option<Entity>,
+    pub timer: Timer,
+    pub tick_timer: Timer,
+}
+
+impl StatusEffect {
+    pub fn new(kind: StatusEffectKind, magnitude: f32, duration: f32) -> Self {
+        StatusEffect {
+            kind,
+            magnitude,
+            stacks: 1,
+            source: None,
+            timer: Timer::from_seconds(duration, TimerMode::Once),
+            tick_timer: Timer::from_seconds(DAMAGE_OVER_TIME_TICK, TimerMode::Repeating),
+        }
+    }
+
+    pub fn slow(fraction: f32, duration: f32) -> Self {
+        StatusEffect::new(StatusEffectKind::Slow, fraction, duration)
+    }
+
+    pub fn stun(duration: f32) -> Self {
+        StatusEffect::new(StatusEffectKind::Stun, 0.0, duration)
+    }
+
+    pub fn poison(damage_per_tick: f32, duration: f32) -> Self {
+        StatusEffect::new(StatusEffectKind::Poison, damage_per_tick, duration)
+    }
+
+    pub fn burn(damage_per_tick: f32, duration: f32) -> Self {
+        StatusEffect::new(StatusEffectKind::Burn, damage_per_tick, duration)
+    }
+
+    pub fn fear(duration: f32) -> Self {
+        StatusEffect::new(StatusEffectKind::Fear, 0.0, duration)
+    }
+}
+
+#[derive(Component, Default, Clone, Debug)]
+pub struct StatusEffects(pub Vec<StatusEffect>);
+
+impl StatusEffects {
+    pub fn get(&self, kind: StatusEffectKind) -> Option<&StatusEffect> {
+        self.0.iter().find(|effect| effect.kind == kind)
+    }
+
+    pub fn has(&self, kind: StatusEffectKind) -> bool {
+        self.get(kind).is_some()
+    }
+
+    pub fn add(&mut self, effect: StatusEffect) {
+        let Some(existing) = self
+            .0
+            .iter_mut()
+            .find(|existing| existing.kind == effect.kind)
+        else {
+            self.0.push(effect);
+            return;
+        };
+
+        match effect.kind.stacking_rule() {
+            StackingRule::Refresh => {
+                existing.magnitude = existing.magnitude.max(effect.magnitude);
+                if effect.timer.duration() > existing.timer.remaining() {
+                    existing.timer = effect.timer;
+                }
+            }
+            StackingRule::Stack { max_stacks } => {
+                existing.stacks = (existing.stacks + effect.stacks).min(max_stacks);
+                existing.magnitude = existing.magnitude.max(effect.magnitude);
+                existing.timer = effect.timer;
+            }
+        }
+
+        existing.source = effect.source.or(existing.source);
+    }
+}
+
+// Effects that are applied to whatever the unit damages with its regular attacks
+#[derive(Component, Default, Clone, Debug)]
+pub struct OnHitStatusEffects(pub Vec<StatusEffect>);
+
+#[derive(Event, Clone, Debug)]
+pub struct ApplyStatusEffect {
+    pub source: Option<Entity>,
+    pub target: Entity,
+    pub effect: StatusEffect,
+}
+
+pub fn apply_on_hit_status_effects(
+    mut damaged_events: EventReader<UnitDamaged>,
+    mut status_events: EventWriter<ApplyStatusEffect>,
+    query: Query<&OnHitStatusEffects>,
+) {
+    for event in damaged_events.read() {
+        // Damage over time ticks would otherwise keep re-applying themselves
+        if event.kind != DamageKind::Physical {
+            continue;
+        }
+
+        let Some(on_hit) = event.source.and_then(|source| query.get(source).ok()) else {
+            continue;
+        };
+
+        for effect in on_hit.0.iter() {
+            status_events.send(ApplyStatusEffect {
+                source: event.source,
+                target: event.target,
+                effect: effect.clone(),
+            });
+        }
+    }
+}
+
+pub fn apply_status_effects(
+    mut status_events: EventReader<ApplyStatusEffect>,
+    mut query: Query<(&mut StatusEffects, &Health)>,
+) {
+    for event in status_events.read() {
+        let Ok((mut status_effects, health)) = query.get_mut(event.target) else {
+            continue;
+        };
+
+        if health.is_dead() {
+            continue;
+        }
+
+        let mut effect = event.effect.clone();
+        effect.source = event.source;
+        status_effects.add(effect);
+    }
+}
+
+pub fn update_status_effects(
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut StatusEffects, &mut StatModifiers, &Health)>,
+    mut damage_events: EventWriter<DamageEvent>,
+) {
+    for (entity, mut status_effects, mut stat_modifiers, health) in query.iter_mut() {
+        if health.is_dead() {
+            status_effects.0.clear();
+        }
+
+        for effect in status_effects.0.iter_mut() {
+            effect.timer.tick(time.delta());
+
+            let Some(damage_kind) = effect.kind.damage_kind() else {
+                continue;
+            };
+
+            let ticks = effect
+                .tick_timer
+                .tick(time.delta())
+                .times_finished_this_tick();
+            for _ in 0..ticks {
+                damage_events.send(DamageEvent {
+                    source: effect.source,
+                    target: entity,
+                    amount: (effect.magnitude * effect.stacks as f32).round().max(1.0) as u32,
+                    kind: damage_kind,
+                });
+            }
+        }
+
+        status_effects.0.retain(|effect| !effect.timer.finished());
+
+        // Only touch the modifiers when the slow actually changes, it triggers a stat recalculation
+        let slow_multiplier = status_effects
+            .get(StatusEffectKind::Slow)
+            .map(|slow| 1.0 - slow.magnitude.clamp(0.0, 1.0));
+        let current_multiplier = stat_modifiers
+            .0
+            .iter()
+            .find(|modifier| modifier.source == SLOW_MODIFIER_SOURCE)
+            .map(|modifier| modifier.kind);
+
+        match slow_multiplier {
+            Some(multiplier)
+                if current_multiplier != Some(ModifierKind::Multiplicative(multiplier)) =>
+            {
+                stat_modifiers.add(StatModifier::new(
+                    Stat::Speed,
+                    ModifierKind::Multiplicative(multiplier),
+                    SLOW_MODIFIER_SOURCE,
+                ));
+            }
+            None if current_multiplier.is_some() => {
+                stat_modifiers.remove_source(SLOW_MODIFIER_SOURCE);
+            }
+            _ => {}
+        }
+    }
+}
+
+pub fn update_status_tints(
+    query: Query<(&StatusEffects, &BaseTint, &Children, Has<Invulnerable>)>,
+    mut child_query: Query<&mut Sprite, With<Animation>>,
+) {
+    for (status_effects, base_tint, children, is_invulnerable) in query.iter() {
+        let mut tint = TINT_PRIORITY
+            .iter()
+            .find(|kind| status_effects.has(**kind))
+            .map(|kind| kind.tint())
+            .unwrap_or(base_tint.0);
+
+        // Invulnerable units fade out a bit, so it's clear hits won't land
+        if is_invulnerable {
+            tint.set_a(INVULNERABLE_ALPHA);
+        }
+
+        for child in children.iter() {
+            if let Ok(mut sprite) = child_query.get_mut(*child) {
+                if sprite.color != tint {
+                    sprite.color = tint;
+                }
+            }
+        }
+    }
+}
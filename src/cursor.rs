@@ -0,0 +1,58 @@
+use bevy::prelude::*;
+
+#[derive(Component)]
+pub struct CustomCursor;
+
+// Where the mouse is in world space, None when it is outside of the window
+#[derive(Resource, Default)]
+pub struct CursorWorldPosition(pub Option<Vec2>);
+
+pub fn update_cursor_world_position(
+    mut cursor_world_position: ResMut<CursorWorldPosition>,
+    window_query: Query<&Window>,
+    camera_query: Query<(&Camera, &GlobalTransform)>,
+) {
+    let window = window_query.single();
+    let Some((camera, camera_transform)) = camera_query.iter().next() else {
+        return;
+    };
+
+    cursor_world_position.0 = window
+        .cursor_position()
+        .and_then(|position| camera.viewport_to_world_2d(camera_transform, position));
+}
+
+// The system cursor stays hidden, this is drawn in its place
+pub fn setup_custom_cursor(mut commands: Commands) {
+    commands.spawn((
+        SpriteBundle {
+            sprite: Sprite {
+                color: Color::rgb(0.7, 0.3, 1.0),
+                custom_size: Some(Vec2::splat(12.0)),
+                ..default()
+            },
+            transform: Transform::from_rotation(Quat::from_rotation_z(std::f32::consts::FRAC_PI_4)),
+            visibility: Visibility::Hidden,
+            ..default()
+        },
+        CustomCursor,
+    ));
+}
+
+pub fn update_custom_cursor(
+    cursor_world_position: Res<CursorWorldPosition>,
+    mut query: Query<(&mut Transform, &mut Visibility), With<CustomCursor>>,
+) {
+    let Ok((mut transform, mut visibility)) = query.get_single_mut() else {
+        return;
+    };
+
+    match cursor_world_position.0 {
+        Some(position) => {
+            // Draw it on top of everything else
+            transform.translation = position.extend(100.0);
+            *visibility = Visibility::Visible;
+        }
+        None => *visibility = Visibility::Hidden,
+    }
+}
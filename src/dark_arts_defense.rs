@@ -2,11 +2,16 @@ use bevy::prelude::*;
 
 use crate::ai;
 use crate::animation;
+use crate::collision;
+use crate::combat;
+use crate::cursor;
 use crate::enemies;
 use crate::gamestate;
+use crate::pickups;
 use crate::player;
+use crate::structures;
 use crate::ui;
-use crate::units::acolyte;
+use crate::units::{acolyte, stats, veterancy};
 use crate::velocity;
 use rand::{rngs::StdRng, SeedableRng};
 
@@ -17,7 +22,6 @@ pub struct RandomSeed(pub StdRng);
 pub enum GameEvent {
     StartGame,
     GameOver,
-    IncreaseScore,
 }
 
 pub struct DarkArtsDefensePlugin;
@@ -25,25 +29,50 @@ pub struct DarkArtsDefensePlugin;
 impl Plugin for DarkArtsDefensePlugin {
     fn build(&self, app: &mut App) {
         app.insert_resource(RandomSeed(StdRng::seed_from_u64(12345123454321_u64)))
+            .init_resource::<cursor::CursorWorldPosition>()
+            .init_resource::<gamestate::GameMode>()
             .add_plugins((
                 player::plugin::PlayerPlugin,
                 enemies::plugin::EnemyPlugin,
                 ai::plugin::AiPlugin,
+                combat::plugin::CombatPlugin,
+                structures::plugin::StructurePlugin,
                 ui::plugin::UiPlugin,
             ))
             .add_event::<GameEvent>()
-            .add_systems(Startup, gamestate::init_game_system)
+            .add_systems(
+                Startup,
+                (gamestate::init_game_system, cursor::setup_custom_cursor),
+            )
             .add_systems(
                 Update,
                 (
-                    gamestate::start_game_system,
-                    gamestate::game_over_system,
-                    gamestate::update_score_system,
+                    cursor::update_cursor_world_position,
+                    cursor::update_custom_cursor.after(cursor::update_cursor_world_position),
+                    (
+                        gamestate::start_game_system,
+                        gamestate::game_over_system,
+                        gamestate::update_score_system,
+                        gamestate::toggle_game_mode_system,
+                    ),
                     animation::animation_state_machine,
                     animation::update_animation_visibility,
                     animation::animate_sprite,
                     velocity::translate,
+                    collision::resolve_collisions.after(velocity::translate),
                     acolyte::acolyte_mana_giver,
+                    pickups::drop_orbs.after(combat::damage::resolve_damage),
+                    pickups::update_orbs.before(combat::damage::resolve_healing),
+                    veterancy::track_veterancy.after(combat::damage::resolve_damage),
+                    veterancy::promote_veterans
+                        .after(veterancy::track_veterancy)
+                        .before(stats::apply_stat_modifiers),
+                    stats::init_base_stats,
+                    stats::tick_stat_modifiers,
+                    stats::apply_stat_modifiers
+                        .after(stats::init_base_stats)
+                        .after(stats::tick_stat_modifiers)
+                        .before(velocity::translate),
                 ),
             );
     }
@@ -0,0 +1,180 @@
+use bevy::ecs::system::EntityCommands;
+use bevy::prelude::*;
+
+use crate::ai::ability::{Abilities, Ability, AbilityEffect, AbilityTargeting};
+use crate::combat::damage::DamageKind;
+use crate::enemies::enemy_spawner::spawn_enemy;
+use crate::units::health::Health;
+use crate::units::stats::{ModifierKind, Stat, StatModifier, StatModifiers};
+use crate::units::unit_types::UnitType;
+
+const BOSS_PHASE_SOURCE: &str = "boss_phase";
+const ADD_SPAWN_DISTANCE: f32 = 96.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BossKind {
+    Warlord,
+}
+
+// Entered once the boss drops to the health fraction, each phase builds on the ones before it
+#[derive(Debug, Clone)]
+pub struct BossPhase {
+    pub health_fraction: f32,
+    pub abilities: Vec<Ability>,
+    pub adds: Vec<(UnitType, usize)>,
+    pub modifiers: Vec<(Stat, f32)>, // Multipliers that replace the ones from earlier phases
+}
+
+#[derive(Component, Debug, Clone)]
+pub struct Boss {
+    pub kind: BossKind,
+    pub phases: Vec<BossPhase>,
+    pub phase: usize,
+}
+
+impl BossKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            BossKind::Warlord => "Warlord",
+        }
+    }
+
+    fn unit_type(&self) -> UnitType {
+        match self {
+            BossKind::Warlord => UnitType::Warlord,
+        }
+    }
+
+    fn phases(&self) -> Vec<BossPhase> {
+        match self {
+            BossKind::Warlord => vec![
+                BossPhase {
+                    health_fraction: 1.0,
+                    abilities: Vec::new(),
+                    adds: Vec::new(),
+                    modifiers: Vec::new(),
+                },
+                BossPhase {
+                    health_fraction: 0.66,
+                    abilities: vec![Ability::new(
+                        "Ground Slam",
+                        8.0,
+                        1.0,
+                        180.0,
+                        AbilityTargeting::NearbyEnemies { count: 2 },
+                        vec![AbilityEffect::Nova {
+                            radius: 180.0,
+                            amount: 35,
+                            kind: DamageKind::Physical,
+                        }],
+                    )],
+                    adds: vec![(UnitType::Knight, 3)],
+                    modifiers: vec![(Stat::Speed, 1.2)],
+                },
+                BossPhase {
+                    health_fraction: 0.33,
+                    abilities: vec![Ability::new(
+                        "War Cry",
+                        15.0,
+                        0.5,
+                        300.0,
+                        AbilityTargeting::NearbyEnemies { count: 1 },
+                        vec![AbilityEffect::Taunt {
+                            radius: 300.0,
+                            duration: 3.0,
+                            threat: 200.0,
+                        }],
+                    )],
+                    adds: vec![(UnitType::Priest, 2), (UnitType::Paladin, 2)],
+                    modifiers: vec![
+                        (Stat::Speed, 1.2),
+                        (Stat::Damage, 1.3),
+                        (Stat::AttackCooldown, 0.7),
+                    ],
+                },
+            ],
+        }
+    }
+}
+
+impl Boss {
+    pub fn new(kind: BossKind) -> Self {
+        Boss {
+            kind,
+            phases: kind.phases(),
+            phase: 0,
+        }
+    }
+}
+
+pub fn spawn_boss<'a>(
+    commands: &'a mut Commands,
+    asset_server: &'a Res<AssetServer>,
+    texture_atlas_layouts: &'a mut ResMut<Assets<TextureAtlasLayout>>,
+    kind: BossKind,
+    position: Vec2,
+) -> EntityCommands<'a> {
+    let mut boss = spawn_enemy(
+        commands,
+        asset_server,
+        texture_atlas_layouts,
+        kind.unit_type(),
+        position,
+    );
+    boss.insert(Boss::new(kind));
+    boss
+}
+
+pub fn update_boss_phases(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+    mut query: Query<(
+        &mut Boss,
+        &Health,
+        &Transform,
+        &mut Abilities,
+        &mut StatModifiers,
+    )>,
+) {
+    for (mut boss, health, transform, mut abilities, mut stat_modifiers) in query.iter_mut() {
+        if health.is_dead() {
+            continue;
+        }
+
+        // A big enough hit can skip straight past a phase, it still gets entered on the way
+        while boss
+            .phases
+            .get(boss.phase + 1)
+            .is_some_and(|next| health.fraction() <= next.health_fraction)
+        {
+            boss.phase += 1;
+            let phase = boss.phases[boss.phase].clone();
+
+            abilities.0.extend(phase.abilities);
+            for (stat, multiplier) in phase.modifiers {
+                stat_modifiers.add(StatModifier::new(
+                    stat,
+                    ModifierKind::Multiplicative(multiplier),
+                    BOSS_PHASE_SOURCE,
+                ));
+            }
+
+            let adds = phase
+                .adds
+                .iter()
+                .flat_map(|(unit_type, count)| std::iter::repeat_n(*unit_type, *count))
+                .collect::<Vec<UnitType>>();
+            for (index, unit_type) in adds.iter().enumerate() {
+                let angle = std::f32::consts::TAU * index as f32 / adds.len() as f32;
+                spawn_enemy(
+                    &mut commands,
+                    &asset_server,
+                    &mut texture_atlas_layouts,
+                    *unit_type,
+                    transform.translation.truncate() + Vec2::from_angle(angle) * ADD_SPAWN_DISTANCE,
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,143 @@
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::animation::BaseTint;
+use crate::combat::damage::{Armor, HealEvent, UnitDamaged, UnitDied};
+use crate::dark_arts_defense::RandomSeed;
+use crate::enemies::enemy_spawner::spawn_enemy;
+use crate::units::stats::{ModifierKind, Stat, StatModifier, StatModifiers};
+use crate::units::unit_types::UnitType;
+
+const ELITE_SOURCE: &str = "elite";
+const ELITE_SCALE: f32 = 1.2;
+const VAMPIRIC_LIFESTEAL: f32 = 0.3;
+const SPLIT_COUNT: usize = 2;
+const SPLIT_OFFSET: f32 = 32.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EliteAffix {
+    Fast,      // Moves and attacks faster, with a little more health
+    Armored,   // More armor and health
+    Vampiric,  // Heals for part of the damage it deals
+    Splitting, // Breaks up into scouts when it dies
+}
+
+impl EliteAffix {
+    pub fn random(rng: &mut RandomSeed) -> Self {
+        match rng.0.gen_range(0..4) {
+            0 => EliteAffix::Fast,
+            1 => EliteAffix::Armored,
+            2 => EliteAffix::Vampiric,
+            _ => EliteAffix::Splitting,
+        }
+    }
+
+    fn tint(&self) -> Color {
+        match self {
+            EliteAffix::Fast => Color::rgb(1.0, 1.0, 0.4),
+            EliteAffix::Armored => Color::rgb(0.6, 0.7, 0.9),
+            EliteAffix::Vampiric => Color::rgb(0.8, 0.2, 0.3),
+            EliteAffix::Splitting => Color::rgb(0.5, 1.0, 0.5),
+        }
+    }
+
+    fn modifiers(&self) -> Vec<(Stat, f32)> {
+        match self {
+            EliteAffix::Fast => vec![
+                (Stat::MaxHealth, 1.25),
+                (Stat::Speed, 1.6),
+                (Stat::AttackCooldown, 0.75),
+            ],
+            EliteAffix::Armored => vec![(Stat::MaxHealth, 1.9)],
+            EliteAffix::Vampiric => vec![(Stat::MaxHealth, 1.25), (Stat::Damage, 1.2)],
+            EliteAffix::Splitting => vec![(Stat::MaxHealth, 1.5)],
+        }
+    }
+}
+
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Elite(pub EliteAffix);
+
+pub fn init_elites(
+    mut query: Query<
+        (
+            &Elite,
+            &mut StatModifiers,
+            &mut Armor,
+            &mut BaseTint,
+            &mut Transform,
+        ),
+        Added<Elite>,
+    >,
+) {
+    for (elite, mut stat_modifiers, mut armor, mut base_tint, mut transform) in query.iter_mut() {
+        for (stat, multiplier) in elite.0.modifiers() {
+            stat_modifiers.add(StatModifier::new(
+                stat,
+                ModifierKind::Multiplicative(multiplier),
+                ELITE_SOURCE,
+            ));
+        }
+
+        if elite.0 == EliteAffix::Armored {
+            armor.0 += 4;
+        }
+
+        base_tint.0 = elite.0.tint();
+        transform.scale *= ELITE_SCALE;
+    }
+}
+
+pub fn vampiric_lifesteal(
+    mut damaged_events: EventReader<UnitDamaged>,
+    query: Query<&Elite>,
+    mut heal_events: EventWriter<HealEvent>,
+) {
+    for event in damaged_events.read() {
+        let Some(source) = event.source else {
+            continue;
+        };
+
+        if !query
+            .get(source)
+            .is_ok_and(|elite| elite.0 == EliteAffix::Vampiric)
+        {
+            continue;
+        }
+
+        heal_events.send(HealEvent {
+            source: Some(source),
+            target: source,
+            amount: ((event.amount as f32 * VAMPIRIC_LIFESTEAL).round() as u32).max(1),
+        });
+    }
+}
+
+pub fn split_elites(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+    mut died_events: EventReader<UnitDied>,
+    query: Query<(&Elite, &Transform)>,
+) {
+    for event in died_events.read() {
+        let Ok((elite, transform)) = query.get(event.target) else {
+            continue;
+        };
+
+        if elite.0 != EliteAffix::Splitting {
+            continue;
+        }
+
+        for index in 0..SPLIT_COUNT {
+            let angle = std::f32::consts::TAU * index as f32 / SPLIT_COUNT as f32;
+            spawn_enemy(
+                &mut commands,
+                &asset_server,
+                &mut texture_atlas_layouts,
+                UnitType::Scout,
+                transform.translation.truncate() + Vec2::from_angle(angle) * SPLIT_OFFSET,
+            );
+        }
+    }
+}
@@ -1,9 +1,16 @@
+use bevy::ecs::system::EntityCommands;
 use bevy::prelude::*;
 use bevy::window::Window;
+use rand::Rng;
 
+use crate::dark_arts_defense::RandomSeed;
+use crate::enemies::boss::{spawn_boss, BossKind};
+use crate::enemies::elite::{Elite, EliteAffix};
 use crate::enemies::plugin::SpawnTimer;
 use crate::units::team::Team;
-use crate::units::unit_types::{spawn_unit, Knight};
+use crate::units::unit_types::{
+    spawn_unit, Archer, Knight, Paladin, Priest, Scout, Siege, UnitType, Warlord,
+};
 
 enum EnemyDirection {
     Top,
@@ -25,22 +32,40 @@ impl EnemyDirection {
 }
 
 const ENEMY_SPAWN_OFFSET: f32 = 256.0;
+const ELITE_CHANCE: f32 = 0.1;
+const WAVE_SIZE: u32 = 15; // Enemies spawned before the next wave starts
+const BOSS_WAVE_INTERVAL: u32 = 5; // Every this many waves a boss shows up
 
-#[derive(Component)]
-pub struct EnemySpawner;
+// How often each enemy shows up compared to the others
+const ENEMY_ROSTER: [(UnitType, u32); 6] = [
+    (UnitType::Knight, 40),
+    (UnitType::Scout, 20),
+    (UnitType::Archer, 15),
+    (UnitType::Priest, 10),
+    (UnitType::Paladin, 8),
+    (UnitType::Siege, 7),
+];
 
+#[derive(Component, Default)]
+pub struct EnemySpawner {
+    pub wave: u32,
+    pub spawned_in_wave: u32,
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn spawn_enemies(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
     time: Res<Time>,
     mut timer: ResMut<SpawnTimer>,
+    mut rng: ResMut<RandomSeed>,
     window_query: Query<&Window>,
-    enemy_spawner_query: Query<&EnemySpawner>,
+    mut enemy_spawner_query: Query<&mut EnemySpawner>,
 ) {
-    if enemy_spawner_query.iter().count() == 0 {
+    let Some(mut enemy_spawner) = enemy_spawner_query.iter_mut().next() else {
         return;
-    }
+    };
 
     if !timer.0.tick(time.delta()).just_finished() {
         return;
@@ -75,12 +100,115 @@ pub fn spawn_enemies(
         ),
     };
 
-    spawn_unit(
+    enemy_spawner.spawned_in_wave += 1;
+    if enemy_spawner.spawned_in_wave >= WAVE_SIZE {
+        enemy_spawner.wave += 1;
+        enemy_spawner.spawned_in_wave = 0;
+
+        if enemy_spawner.wave % BOSS_WAVE_INTERVAL == 0 {
+            spawn_boss(
+                &mut commands,
+                &asset_server,
+                &mut texture_atlas_layouts,
+                BossKind::Warlord,
+                spawn_position,
+            );
+            return;
+        }
+    }
+
+    let unit_type = pick_enemy_type(rng.0.gen::<f32>());
+    let mut enemy = spawn_enemy(
         &mut commands,
         &asset_server,
         &mut texture_atlas_layouts,
-        Knight,
-        Team::Good,
+        unit_type,
         spawn_position,
     );
+
+    if rng.0.gen::<f32>() < ELITE_CHANCE {
+        enemy.insert(Elite(EliteAffix::random(&mut rng)));
+    }
+}
+
+// Walks the weighted roster with a roll between 0 and 1
+fn pick_enemy_type(roll: f32) -> UnitType {
+    let total_weight = ENEMY_ROSTER.iter().map(|(_, weight)| weight).sum::<u32>();
+    let mut remaining = roll * total_weight as f32;
+    for (unit_type, weight) in ENEMY_ROSTER {
+        if remaining < weight as f32 {
+            return unit_type;
+        }
+
+        remaining -= weight as f32;
+    }
+
+    ENEMY_ROSTER[0].0
+}
+
+pub fn spawn_enemy<'a>(
+    commands: &'a mut Commands,
+    asset_server: &'a Res<AssetServer>,
+    texture_atlas_layouts: &'a mut ResMut<Assets<TextureAtlasLayout>>,
+    unit_type: UnitType,
+    position: Vec2,
+) -> EntityCommands<'a> {
+    match unit_type {
+        UnitType::Scout => spawn_unit(
+            commands,
+            asset_server,
+            texture_atlas_layouts,
+            Scout,
+            Team::Good,
+            position,
+        ),
+        UnitType::Archer => spawn_unit(
+            commands,
+            asset_server,
+            texture_atlas_layouts,
+            Archer,
+            Team::Good,
+            position,
+        ),
+        UnitType::Priest => spawn_unit(
+            commands,
+            asset_server,
+            texture_atlas_layouts,
+            Priest,
+            Team::Good,
+            position,
+        ),
+        UnitType::Paladin => spawn_unit(
+            commands,
+            asset_server,
+            texture_atlas_layouts,
+            Paladin,
+            Team::Good,
+            position,
+        ),
+        UnitType::Siege => spawn_unit(
+            commands,
+            asset_server,
+            texture_atlas_layouts,
+            Siege,
+            Team::Good,
+            position,
+        ),
+        UnitType::Warlord => spawn_unit(
+            commands,
+            asset_server,
+            texture_atlas_layouts,
+            Warlord,
+            Team::Good,
+            position,
+        ),
+        _ => spawn_unit(
+            commands,
+            asset_server,
+            texture_atlas_layouts,
+            Knight,
+            Team::Good,
+            position,
+        ),
+    }
 }
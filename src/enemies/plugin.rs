@@ -1,6 +1,8 @@
 use bevy::prelude::*;
 
-use crate::enemies::enemy_spawner;
+use crate::combat::damage;
+use crate::enemies::{boss, elite, enemy_spawner};
+use crate::units::stats;
 
 pub struct EnemyPlugin;
 
@@ -10,6 +12,19 @@ pub struct SpawnTimer(pub Timer);
 impl Plugin for EnemyPlugin {
     fn build(&self, app: &mut App) {
         app.insert_resource(SpawnTimer(Timer::from_seconds(2.0, TimerMode::Repeating)))
-            .add_systems(Update, enemy_spawner::spawn_enemies);
+            .add_systems(
+                Update,
+                (
+                    enemy_spawner::spawn_enemies,
+                    elite::init_elites.before(stats::apply_stat_modifiers),
+                    elite::vampiric_lifesteal
+                        .after(damage::resolve_damage)
+                        .before(damage::resolve_healing),
+                    elite::split_elites.after(damage::resolve_damage),
+                    boss::update_boss_phases
+                        .after(damage::resolve_damage)
+                        .before(stats::apply_stat_modifiers),
+                ),
+            );
     }
 }
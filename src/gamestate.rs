@@ -1,22 +1,42 @@
 use bevy::prelude::*;
 
 use crate::animation::{spawn_animated_children, AnimatedChildSpawnParams, AnimationType};
+use crate::collision::Collider;
+use crate::combat::damage::{Invulnerable, UnitDied};
+use crate::combat::status::StatusEffects;
 use crate::mana::Mana;
 use crate::movement::Movement;
+use crate::player::dodge::Dodge;
 use crate::player::plugin::Player;
+use crate::player::spellbook::{LifeDrainTargets, Spellbook};
+use crate::structures::altar::{spawn_altar, Altar};
 use crate::units::health::Health;
+use crate::units::team::{CurrentTeam, Team};
 use crate::units::unit_types::UnitBundle;
 use crate::{dark_arts_defense::GameEvent, enemies::enemy_spawner::EnemySpawner};
 
+const PLAYER_RESPAWN_TIME: f32 = 5.0;
+const PLAYER_RESPAWN_OFFSET: Vec2 = Vec2::new(0.0, -96.0); // From the altar
+const PLAYER_RESPAWN_INVULNERABILITY: f32 = 2.0;
+
 #[derive(Component, Default)]
 pub struct Cleanup;
 
+// Rules that apply to the whole game rather than to single units
+#[derive(Resource, Default)]
+pub struct GameMode {
+    pub friendly_fire: bool,   // Area attacks can hurt the attacker's allies
+    pub altar_only_loss: bool, // The player comes back after dying, only losing the altar ends the run
+    pub summon_upkeep: bool,   // Living summons keep draining mana
+}
+
 #[derive(Component)]
 pub struct GameState {
     pub game_over: bool,
     pub show_end_timer: Timer,
     pub score: u32,
     pub end_screen_active: bool,
+    pub player_respawn_timer: Timer,
 }
 
 impl Default for GameState {
@@ -26,43 +46,101 @@ impl Default for GameState {
             show_end_timer: Timer::from_seconds(5.0, TimerMode::Once),
             score: 0,
             end_screen_active: false,
+            player_respawn_timer: Timer::from_seconds(PLAYER_RESPAWN_TIME, TimerMode::Once),
         }
     }
 }
 
+// Game mode rules can be switched at any time, the HUD shows which ones are on
+pub fn toggle_game_mode_system(keys: Res<ButtonInput<KeyCode>>, mut game_mode: ResMut<GameMode>) {
+    if keys.just_pressed(KeyCode::F1) {
+        game_mode.summon_upkeep = !game_mode.summon_upkeep;
+    }
+
+    if keys.just_pressed(KeyCode::F2) {
+        game_mode.altar_only_loss = !game_mode.altar_only_loss;
+    }
+
+    if keys.just_pressed(KeyCode::F3) {
+        game_mode.friendly_fire = !game_mode.friendly_fire;
+    }
+}
+
 pub fn init_game_system(mut commands: Commands, mut events: EventWriter<GameEvent>) {
     commands.spawn(Camera2dBundle::default());
     events.send(crate::dark_arts_defense::GameEvent::StartGame);
 }
 
+#[allow(clippy::type_complexity)]
 pub fn game_over_system(
+    mut commands: Commands,
     time: Res<Time>,
-    query: Query<&Health, With<Player>>,
+    game_mode: Res<GameMode>,
+    mut player_query: Query<
+        (Entity, &mut Health, &mut Transform, &mut StatusEffects),
+        (With<Player>, Without<Altar>),
+    >,
+    altar_query: Query<(&Health, &Transform), With<Altar>>,
     mut game_state_query: Query<&mut GameState>,
 ) {
-    if let Some(health) = query.iter().next() {
-        if health.is_dead() {
-            for mut state in game_state_query.iter_mut() {
-                state.game_over = true;
-                state.show_end_timer.tick(time.delta());
-                if state.show_end_timer.just_finished() {
-                    state.end_screen_active = true;
-                }
+    let is_altar_destroyed = altar_query.iter().any(|(health, _)| health.is_dead());
+    let altar_position = altar_query
+        .iter()
+        .next()
+        .map_or(Vec2::ZERO, |(_, transform)| {
+            transform.translation.truncate()
+        });
+    for mut state in game_state_query.iter_mut() {
+        let mut is_lost = is_altar_destroyed;
+        for (entity, mut health, mut transform, mut status_effects) in player_query.iter_mut() {
+            if !health.is_dead() || state.game_over {
+                continue;
+            }
+
+            if !game_mode.altar_only_loss {
+                is_lost = true;
+                continue;
+            }
+
+            // Back next to the altar with a clean slate, and a moment to get away before being hit
+            if state.player_respawn_timer.tick(time.delta()).finished() {
+                health.current = health.max;
+                let position = altar_position + PLAYER_RESPAWN_OFFSET;
+                transform.translation = position.extend(transform.translation.z);
+                status_effects.0.clear();
+                commands
+                    .entity(entity)
+                    .insert(Invulnerable::from_seconds(PLAYER_RESPAWN_INVULNERABILITY));
+                state.player_respawn_timer.reset();
+            }
+        }
+
+        if is_lost || state.game_over {
+            state.game_over = true;
+            state.show_end_timer.tick(time.delta());
+            if state.show_end_timer.just_finished() {
+                state.end_screen_active = true;
             }
         }
     }
 }
 
 pub fn update_score_system(
-    mut event_reader: EventReader<GameEvent>,
+    mut died_events: EventReader<UnitDied>,
+    team_query: Query<&CurrentTeam>,
     mut query: Query<&mut GameState>,
 ) {
-    for event in event_reader.read() {
-        if let GameEvent::IncreaseScore = event {
-            for mut state in query.iter_mut() {
-                if !state.game_over {
-                    state.score += 10;
-                }
+    for event in died_events.read() {
+        let is_enemy = team_query
+            .get(event.target)
+            .is_ok_and(|team| team.0 == Team::Good);
+        if !is_enemy {
+            continue;
+        }
+
+        for mut state in query.iter_mut() {
+            if !state.game_over {
+                state.score += 10;
             }
         }
     }
@@ -80,12 +158,14 @@ pub fn start_game_system(
             cleanup_game_system(&mut commands, &cleanup_char_query);
 
             commands.spawn((GameState::default(), Cleanup {}));
-            commands.spawn((EnemySpawner {}, Cleanup {}));
+            commands.spawn((EnemySpawner::default(), Cleanup {}));
+            spawn_altar(&mut commands);
 
             commands
                 .spawn((
                     UnitBundle {
                         movement: Movement { speed: 150.0 },
+                        collider: Collider { radius: 24.0 },
                         transform: Transform::from_scale(Vec3::splat(2.0)),
                         ..default()
                     },
@@ -94,6 +174,9 @@ pub fn start_game_system(
                         current_mana: 100,
                         max_mana: 100,
                     },
+                    Spellbook::default(),
+                    LifeDrainTargets::default(),
+                    Dodge::default(),
                 ))
                 .with_children(|parent| {
                     let children_params: Vec<AnimatedChildSpawnParams> = [
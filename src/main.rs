@@ -1,33 +1,69 @@
 pub mod animation;
+pub mod collision;
+pub mod combat {
+    pub mod area;
+    pub mod damage;
+    pub mod plugin;
+    pub mod projectile;
+    pub mod status;
+}
+pub mod cursor;
 pub mod dark_arts_defense;
 pub mod player {
+    pub mod commands;
+    pub mod dodge;
+    pub mod guard;
     pub mod movement;
     pub mod plugin;
+    pub mod sacrifice;
+    pub mod selection;
     pub mod spawn;
+    pub mod spellbook;
     pub mod summoning;
 }
 pub mod units {
     pub mod acolyte;
     pub mod health;
+    pub mod stats;
     pub mod team;
     pub mod unit_types;
+    pub mod veterancy;
 }
 pub mod enemies {
+    pub mod boss;
+    pub mod elite;
     pub mod enemy_spawner;
     pub mod plugin;
 }
 pub mod mana;
 pub mod movement;
+pub mod pickups;
 pub mod velocity;
 pub mod ai {
+    pub mod ability;
     pub mod behavior;
+    pub mod command;
+    pub mod plugin;
+    pub mod steering;
+    pub mod targeting;
+    pub mod threat;
+}
+pub mod structures {
+    pub mod altar;
     pub mod plugin;
+    pub mod structure;
 }
 pub mod ui {
+    pub mod boss_bar;
+    pub mod command_text;
+    pub mod damage_text;
     pub mod health_text;
     pub mod mana_text;
+    pub mod mode_text;
     pub mod plugin;
     pub mod score_text;
+    pub mod spell_text;
+    pub mod summon_text;
 }
 pub mod gamestate;
 
@@ -2,6 +2,6 @@ use bevy::prelude::*;
 
 #[derive(Component)]
 pub struct Mana {
-    pub current_mana: u8,
-    pub max_mana: u8,
+    pub current_mana: u32,
+    pub max_mana: u32,
 }
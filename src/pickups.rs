@@ -0,0 +1,160 @@
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::collision::Collider;
+use crate::combat::damage::{HealEvent, UnitDied};
+use crate::dark_arts_defense::RandomSeed;
+use crate::gamestate::Cleanup;
+use crate::mana::Mana;
+use crate::player::plugin::Player;
+use crate::units::health::Health;
+use crate::units::team::{CurrentTeam, Team};
+
+const MANA_ORB_AMOUNT: u32 = 10;
+const HEALTH_ORB_AMOUNT: u32 = 20;
+const HEALTH_ORB_CHANCE: f32 = 0.25;
+
+const ORB_SIZE: f32 = 12.0;
+const ORB_LIFETIME: f32 = 10.0;
+const ORB_FADE_TIME: f32 = 3.0; // Orbs fade out over the last seconds of their lifetime
+const ORB_MAGNET_RADIUS: f32 = 160.0;
+const ORB_MAGNET_SPEED: f32 = 450.0;
+const ORB_SCATTER: f32 = 24.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrbKind {
+    Mana,
+    Health,
+}
+
+impl OrbKind {
+    fn color(&self) -> Color {
+        match self {
+            OrbKind::Mana => Color::rgb(0.3, 0.5, 1.0),
+            OrbKind::Health => Color::rgb(0.3, 0.9, 0.3),
+        }
+    }
+}
+
+#[derive(Component, Debug, Clone)]
+pub struct Orb {
+    pub kind: OrbKind,
+    pub amount: u32,
+    pub lifetime_timer: Timer,
+}
+
+fn spawn_orb(commands: &mut Commands, kind: OrbKind, amount: u32, position: Vec2) {
+    commands.spawn((
+        SpriteBundle {
+            sprite: Sprite {
+                color: kind.color(),
+                custom_size: Some(Vec2::splat(ORB_SIZE)),
+                ..default()
+            },
+            transform: Transform::from_translation(position.extend(10.0))
+                .with_rotation(Quat::from_rotation_z(std::f32::consts::FRAC_PI_4)),
+            ..default()
+        },
+        Orb {
+            kind,
+            amount,
+            lifetime_timer: Timer::from_seconds(ORB_LIFETIME, TimerMode::Once),
+        },
+        Cleanup,
+    ));
+}
+
+// Every slain enemy leaves mana behind, and sometimes a bit of health too
+pub fn drop_orbs(
+    mut commands: Commands,
+    mut rng: ResMut<RandomSeed>,
+    mut died_events: EventReader<UnitDied>,
+    query: Query<(&Transform, &CurrentTeam)>,
+) {
+    for event in died_events.read() {
+        let Ok((transform, team)) = query.get(event.target) else {
+            continue;
+        };
+
+        if team.0 != Team::Good {
+            continue;
+        }
+
+        let position = transform.translation.truncate();
+        let drops_health = rng.0.gen::<f32>() < HEALTH_ORB_CHANCE;
+        let mut scatter = || {
+            Vec2::new(
+                rng.0.gen_range(-ORB_SCATTER..=ORB_SCATTER),
+                rng.0.gen_range(-ORB_SCATTER..=ORB_SCATTER),
+            )
+        };
+        spawn_orb(
+            &mut commands,
+            OrbKind::Mana,
+            MANA_ORB_AMOUNT,
+            position + scatter(),
+        );
+
+        if drops_health {
+            spawn_orb(
+                &mut commands,
+                OrbKind::Health,
+                HEALTH_ORB_AMOUNT,
+                position + scatter(),
+            );
+        }
+    }
+}
+
+pub fn update_orbs(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut Orb, &mut Transform, &mut Sprite), Without<Player>>,
+    mut player_query: Query<(Entity, &Transform, &Collider, &Health, &mut Mana), With<Player>>,
+    mut heal_events: EventWriter<HealEvent>,
+) {
+    let mut player = player_query
+        .iter_mut()
+        .next()
+        .filter(|(_, _, _, health, _)| !health.is_dead());
+
+    for (entity, mut orb, mut transform, mut sprite) in query.iter_mut() {
+        if orb.lifetime_timer.tick(time.delta()).finished() {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+
+        let remaining = orb.lifetime_timer.remaining_secs();
+        sprite.color.set_a((remaining / ORB_FADE_TIME).min(1.0));
+
+        let Some((player_entity, player_transform, player_collider, _, ref mut mana)) = player
+        else {
+            continue;
+        };
+
+        let offset = player_transform.translation.truncate() - transform.translation.truncate();
+        if offset.length() <= player_collider.radius + ORB_SIZE {
+            match orb.kind {
+                OrbKind::Mana => {
+                    mana.current_mana = (mana.current_mana + orb.amount).min(mana.max_mana);
+                }
+                OrbKind::Health => {
+                    heal_events.send(HealEvent {
+                        source: None,
+                        target: player_entity,
+                        amount: orb.amount,
+                    });
+                }
+            }
+
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+
+        // Orbs close to the player are pulled in, so they don't have to be picked up exactly
+        if offset.length() <= ORB_MAGNET_RADIUS {
+            let step = (ORB_MAGNET_SPEED * time.delta_seconds()).min(offset.length());
+            transform.translation += (offset.normalize_or_zero() * step).extend(0.0);
+        }
+    }
+}
@@ -0,0 +1,119 @@
+use bevy::prelude::*;
+
+use crate::ai::behavior::GuardBehavior;
+use crate::ai::command::{CurrentCommand, UnitCommand};
+use crate::cursor::CursorWorldPosition;
+use crate::units::team::{CurrentTeam, Team};
+use crate::units::unit_types::UnitType;
+
+use super::plugin::Player;
+use super::selection::Selected;
+
+// Which summons the next command is given to
+#[derive(Resource, Default, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CommandScope {
+    #[default]
+    All,
+    UnitType(UnitType),
+}
+
+impl CommandScope {
+    fn next(&self) -> Self {
+        match self {
+            CommandScope::All => CommandScope::UnitType(UnitType::Acolyte),
+            CommandScope::UnitType(UnitType::Acolyte) => CommandScope::UnitType(UnitType::Warrior),
+            CommandScope::UnitType(UnitType::Warrior) => CommandScope::UnitType(UnitType::Cat),
+            CommandScope::UnitType(UnitType::Cat) => CommandScope::UnitType(UnitType::Warlock),
+            CommandScope::UnitType(UnitType::Warlock) => CommandScope::UnitType(UnitType::Mender),
+            CommandScope::UnitType(_) => CommandScope::All,
+        }
+    }
+
+    pub fn includes(&self, unit_type: &UnitType) -> bool {
+        match self {
+            CommandScope::All => true,
+            CommandScope::UnitType(scope_unit_type) => scope_unit_type == unit_type,
+        }
+    }
+}
+
+enum CommandInput {
+    AttackMove,
+    HoldPosition,
+    FollowMe,
+    Retreat,
+    Cancel,
+}
+
+#[allow(clippy::type_complexity)]
+pub fn system(
+    keys: Res<ButtonInput<KeyCode>>,
+    cursor_world_position: Res<CursorWorldPosition>,
+    mut scope: ResMut<CommandScope>,
+    mut query: Query<
+        (
+            &mut CurrentCommand,
+            Option<&mut GuardBehavior>,
+            &UnitType,
+            &CurrentTeam,
+            &Transform,
+            Has<Selected>,
+        ),
+        Without<Player>,
+    >,
+) {
+    if keys.just_pressed(KeyCode::Tab) {
+        *scope = scope.next();
+    }
+
+    let row_staggered_qwerty_binds = [
+        (KeyCode::KeyZ, CommandInput::AttackMove),
+        (KeyCode::KeyX, CommandInput::HoldPosition),
+        (KeyCode::KeyC, CommandInput::FollowMe),
+        (KeyCode::KeyV, CommandInput::Retreat),
+        (KeyCode::KeyB, CommandInput::Cancel),
+    ];
+
+    let Some((_, command_input)) = row_staggered_qwerty_binds
+        .iter()
+        .find(|(key, _)| keys.just_pressed(*key))
+    else {
+        return;
+    };
+
+    // Selected units take precedence over the scope
+    let has_selection = query.iter().any(|(_, _, _, _, _, is_selected)| is_selected);
+    for (mut current_command, guard_behavior, unit_type, team, transform, is_selected) in
+        query.iter_mut()
+    {
+        let is_commanded = if has_selection {
+            is_selected
+        } else {
+            scope.includes(unit_type)
+        };
+
+        if team.0 != Team::Evil || !is_commanded {
+            continue;
+        }
+
+        current_command.0 = match command_input {
+            CommandInput::AttackMove => match cursor_world_position.0 {
+                Some(point) => Some(UnitCommand::AttackMove(point)),
+                None => continue,
+            },
+            CommandInput::HoldPosition => {
+                Some(UnitCommand::HoldPosition(transform.translation.truncate()))
+            }
+            CommandInput::FollowMe => Some(UnitCommand::FollowMe),
+            CommandInput::Retreat => Some(UnitCommand::Retreat),
+            CommandInput::Cancel => None,
+        };
+
+        // Coming back to the player means they are no longer guarding anything
+        if let (CommandInput::FollowMe | CommandInput::Retreat, Some(mut guard_behavior)) =
+            (command_input, guard_behavior)
+        {
+            guard_behavior.point = None;
+        }
+    }
+}
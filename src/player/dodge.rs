@@ -0,0 +1,105 @@
+use bevy::prelude::*;
+
+use crate::combat::damage::Invulnerable;
+use crate::combat::status::{StatusEffectKind, StatusEffects};
+use crate::cursor::CursorWorldPosition;
+use crate::units::health::Health;
+use crate::velocity::Velocity;
+
+use super::movement::get_arena_bounds;
+use super::plugin::Player;
+
+const DODGE_KEY: KeyCode = KeyCode::Space;
+const DODGE_COOLDOWN: f32 = 2.0;
+const DODGE_DISTANCE: f32 = 160.0;
+const DODGE_DURATION: f32 = 0.15;
+// Lasts a bit longer than the dodge itself so hits landing right as it ends still miss
+const DODGE_INVULNERABILITY: f32 = 0.3;
+
+#[derive(Component, Debug, Clone)]
+pub struct Dodge {
+    pub cooldown_timer: Timer,
+}
+
+impl Default for Dodge {
+    fn default() -> Self {
+        let mut cooldown_timer = Timer::from_seconds(DODGE_COOLDOWN, TimerMode::Once);
+        cooldown_timer.tick(cooldown_timer.duration());
+        Dodge { cooldown_timer }
+    }
+}
+
+#[derive(Component, Debug, Clone)]
+pub struct Dodging {
+    pub direction: Vec2,
+    pub timer: Timer,
+}
+
+#[allow(clippy::type_complexity)]
+pub fn system(
+    mut commands: Commands,
+    time: Res<Time>,
+    keys: Res<ButtonInput<KeyCode>>,
+    cursor_world_position: Res<CursorWorldPosition>,
+    mut query: Query<
+        (
+            Entity,
+            &mut Dodge,
+            &Velocity,
+            &Transform,
+            &Health,
+            &StatusEffects,
+        ),
+        (With<Player>, Without<Dodging>),
+    >,
+) {
+    for (entity, mut dodge, velocity, transform, health, status_effects) in query.iter_mut() {
+        dodge.cooldown_timer.tick(time.delta());
+        if !keys.just_pressed(DODGE_KEY)
+            || !dodge.cooldown_timer.finished()
+            || health.is_dead()
+            || status_effects.has(StatusEffectKind::Stun)
+        {
+            continue;
+        }
+
+        // Dodge the way the player is moving, or towards the cursor when standing still
+        let towards_cursor = cursor_world_position
+            .0
+            .and_then(|cursor| (cursor - transform.translation.truncate()).try_normalize());
+        let Some(direction) = velocity.0.try_normalize().or(towards_cursor) else {
+            continue;
+        };
+
+        dodge.cooldown_timer.reset();
+        commands.entity(entity).insert((
+            Dodging {
+                direction,
+                timer: Timer::from_seconds(DODGE_DURATION, TimerMode::Once),
+            },
+            Invulnerable::from_seconds(DODGE_INVULNERABILITY),
+        ));
+    }
+}
+
+pub fn update_dodging(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut Dodging, &mut Transform, &mut Velocity), With<Player>>,
+    window_query: Query<&Window>,
+) {
+    let window_bounds = get_arena_bounds(window_query.single());
+
+    for (entity, mut dodging, mut transform, mut velocity) in query.iter_mut() {
+        velocity.0 = Vec2::ZERO;
+
+        let step = dodging.direction * DODGE_DISTANCE / DODGE_DURATION * time.delta_seconds();
+        let position =
+            (transform.translation.truncate() + step).clamp(-window_bounds, window_bounds);
+        transform.translation = position.extend(transform.translation.z);
+
+        if dodging.timer.tick(time.delta()).finished() {
+            commands.entity(entity).remove::<Dodging>();
+        }
+    }
+}
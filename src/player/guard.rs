@@ -0,0 +1,31 @@
+use bevy::prelude::*;
+
+use crate::ai::behavior::GuardBehavior;
+
+use super::plugin::Player;
+
+// Toggles between guarding the spot the player is standing on, and following the player
+pub fn system(
+    keys: Res<ButtonInput<KeyCode>>,
+    player_query: Query<&Transform, With<Player>>,
+    mut query: Query<&mut GuardBehavior>,
+) {
+    if !keys.just_pressed(KeyCode::KeyG) {
+        return;
+    }
+
+    let Some(player_transform) = player_query.iter().next() else {
+        return;
+    };
+
+    let is_guarding = query.iter().any(|guard| guard.point.is_some());
+    let guard_point = if is_guarding {
+        None
+    } else {
+        Some(player_transform.translation.truncate())
+    };
+
+    for mut guard_behavior in query.iter_mut() {
+        guard_behavior.point = guard_point;
+    }
+}
@@ -1,3 +1,4 @@
+use crate::combat::status::{StatusEffectKind, StatusEffects};
 use crate::velocity::Velocity;
 use bevy::prelude::*;
 
@@ -7,7 +8,7 @@ const WINDOW_BOUNDS_OFFSET: f32 = 96.0;
 
 pub fn system(
     keys: Res<ButtonInput<KeyCode>>,
-    query: Query<(&mut Velocity, &Transform), With<Player>>,
+    query: Query<(&mut Velocity, &Transform, &StatusEffects), With<Player>>,
     window_query: Query<&Window>,
 ) {
     // let column_staggered_colemak_binds =
@@ -18,6 +19,14 @@ pub fn system(
     handle_movement(query, window_query, move_input);
 }
 
+// Half the size of the area the player, and everything the player places, has to stay within
+pub fn get_arena_bounds(window: &Window) -> Vec2 {
+    Vec2::new(
+        window.width() - WINDOW_BOUNDS_OFFSET,
+        window.height() - WINDOW_BOUNDS_OFFSET,
+    ) * 0.5
+}
+
 fn construct_input_vector(keys: Res<ButtonInput<KeyCode>>, binds: [KeyCode; 4]) -> Vec2 {
     let mut move_input = Vec2::ZERO;
     if keys.pressed(binds[0]) {
@@ -38,17 +47,18 @@ fn construct_input_vector(keys: Res<ButtonInput<KeyCode>>, binds: [KeyCode; 4])
 }
 
 fn handle_movement(
-    mut query: Query<(&mut Velocity, &Transform), With<Player>>,
+    mut query: Query<(&mut Velocity, &Transform, &StatusEffects), With<Player>>,
     window_query: Query<&Window>,
     move_input: Vec2,
 ) {
-    let window = window_query.single();
-    let window_bounds = Vec2::new(
-        window.width() - WINDOW_BOUNDS_OFFSET,
-        window.height() - WINDOW_BOUNDS_OFFSET,
-    ) * 0.5;
+    let window_bounds = get_arena_bounds(window_query.single());
+
+    for (mut velocity, transform, status_effects) in query.iter_mut() {
+        if status_effects.has(StatusEffectKind::Stun) {
+            velocity.0 = Vec2::ZERO;
+            continue;
+        }
 
-    for (mut velocity, transform) in query.iter_mut() {
         velocity.0 = move_input;
 
         if (transform.translation.x >= window_bounds.x && velocity.0.x > 0.0)
@@ -1,7 +1,12 @@
 use bevy::prelude::*;
 
+use crate::combat::damage;
 use crate::player;
+use crate::player::commands::CommandScope;
+use crate::player::selection::{DefaultRallyPoint, SelectionDrag};
+use crate::player::summoning::{SummonCooldowns, SummonPlacement, UpkeepTimer};
 use crate::units::unit_types::UnitResource;
+use crate::velocity;
 
 pub struct PlayerPlugin;
 
@@ -10,9 +15,48 @@ pub struct Player;
 
 impl Plugin for PlayerPlugin {
     fn build(&self, app: &mut App) {
-        app.insert_resource(UnitResource::default()).add_systems(
-            Update,
-            (player::movement::system, player::summoning::system),
-        );
+        app.insert_resource(UnitResource::default())
+            .init_resource::<CommandScope>()
+            .init_resource::<SelectionDrag>()
+            .init_resource::<DefaultRallyPoint>()
+            .init_resource::<SummonPlacement>()
+            .init_resource::<SummonCooldowns>()
+            .init_resource::<UpkeepTimer>()
+            .add_systems(Startup, player::selection::setup)
+            .add_systems(
+                Update,
+                (
+                    player::movement::system,
+                    player::summoning::system
+                        .after(player::selection::select_units)
+                        .after(player::selection::rally_selected_units),
+                    player::summoning::update_ghost.after(player::summoning::system),
+                    player::guard::system,
+                    player::commands::system,
+                    player::selection::select_units,
+                    player::selection::deselect_dead_units,
+                    player::selection::rally_selected_units,
+                    player::selection::update_rally_flag,
+                    player::selection::add_selection_highlights,
+                    player::selection::remove_selection_highlights,
+                ),
+            )
+            .add_systems(
+                Update,
+                (
+                    player::spellbook::system.before(damage::resolve_damage),
+                    player::spellbook::heal_from_life_drain
+                        .after(damage::resolve_damage)
+                        .before(damage::resolve_healing),
+                    player::spellbook::draw_spell_visuals.after(player::spellbook::system),
+                    player::summoning::drain_upkeep,
+                    player::sacrifice::system,
+                    player::sacrifice::reward_sacrifices.after(player::sacrifice::system),
+                    player::dodge::system.after(player::movement::system),
+                    player::dodge::update_dodging
+                        .after(player::dodge::system)
+                        .before(velocity::translate),
+                ),
+            );
     }
 }
@@ -0,0 +1,163 @@
+use bevy::prelude::*;
+
+use crate::combat::damage::UnitDied;
+use crate::mana::Mana;
+use crate::units::health::Health;
+use crate::units::stats::{ModifierKind, Stat, StatModifier, StatModifiers};
+use crate::units::team::{CurrentTeam, Team};
+use crate::units::unit_types::{UnitResource, UnitType};
+
+use super::plugin::Player;
+use super::selection::Selected;
+
+const SACRIFICE_FOR_MANA_KEY: KeyCode = KeyCode::KeyT;
+const SACRIFICE_FOR_POWER_KEY: KeyCode = KeyCode::KeyY;
+
+const SACRIFICE_RANGE: f32 = 320.0;
+const SACRIFICE_MANA_REFUND: f32 = 0.5; // Fraction of the unit's cost that is given back
+const SACRIFICE_BUFF_RADIUS: f32 = 220.0;
+const SACRIFICE_BUFF_DURATION: f32 = 8.0;
+const SACRIFICE_BUFF_SOURCE: &str = "sacrifice";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SacrificeKind {
+    Mana,  // Gives back part of what the unit cost
+    Power, // Empowers every ally around the unit
+}
+
+// Waiting for the unit to die, the reward is handed out when it does
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Sacrificed(pub SacrificeKind);
+
+// Sacrifices the selected units, or the summon closest to the player when nothing is selected
+#[allow(clippy::type_complexity)]
+pub fn system(
+    mut commands: Commands,
+    keys: Res<ButtonInput<KeyCode>>,
+    player_query: Query<(Entity, &Transform, &Health), With<Player>>,
+    mut units_query: Query<
+        (Entity, &Transform, &CurrentTeam, &mut Health, Has<Selected>),
+        (With<UnitType>, Without<Sacrificed>, Without<Player>),
+    >,
+    mut died_events: EventWriter<UnitDied>,
+) {
+    let kind = if keys.just_pressed(SACRIFICE_FOR_MANA_KEY) {
+        SacrificeKind::Mana
+    } else if keys.just_pressed(SACRIFICE_FOR_POWER_KEY) {
+        SacrificeKind::Power
+    } else {
+        return;
+    };
+
+    let Some((player, player_transform, player_health)) = player_query.iter().next() else {
+        return;
+    };
+
+    if player_health.is_dead() {
+        return;
+    }
+
+    let player_position = player_transform.translation.truncate();
+    let sacrificable = || {
+        units_query
+            .iter()
+            .filter(|(_, _, team, health, _)| team.0 == Team::Evil && !health.is_dead())
+    };
+
+    let mut victims = sacrificable()
+        .filter(|(_, _, _, _, is_selected)| *is_selected)
+        .map(|(entity, _, _, _, _)| entity)
+        .collect::<Vec<_>>();
+
+    if victims.is_empty() {
+        victims = sacrificable()
+            .map(|(entity, transform, _, _, _)| {
+                let distance = (transform.translation.truncate() - player_position).length();
+                (entity, distance)
+            })
+            .filter(|(_, distance)| *distance <= SACRIFICE_RANGE)
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(entity, _)| entity)
+            .into_iter()
+            .collect();
+    }
+
+    // Shields and invulnerability can't save a sacrifice, the unit still dies like any other
+    // so it plays its death animation
+    for victim in victims {
+        let Ok((_, _, _, mut health, _)) = units_query.get_mut(victim) else {
+            continue;
+        };
+
+        health.current = 0;
+        commands.entity(victim).insert(Sacrificed(kind));
+        died_events.send(UnitDied {
+            source: Some(player),
+            target: victim,
+        });
+    }
+}
+
+pub fn reward_sacrifices(
+    mut died_events: EventReader<UnitDied>,
+    unit_configs: Res<UnitResource>,
+    sacrificed_query: Query<(&Sacrificed, &UnitType, &Transform)>,
+    mut player_query: Query<&mut Mana, With<Player>>,
+    mut allies_query: Query<(
+        Entity,
+        &Transform,
+        &CurrentTeam,
+        &Health,
+        &mut StatModifiers,
+    )>,
+) {
+    for event in died_events.read() {
+        let Ok((sacrificed, unit_type, transform)) = sacrificed_query.get(event.target) else {
+            continue;
+        };
+
+        match sacrificed.0 {
+            SacrificeKind::Mana => {
+                let cost = unit_configs
+                    .try_get(*unit_type)
+                    .map_or(0, |config| config.cost);
+                let refund = (cost as f32 * SACRIFICE_MANA_REFUND).round() as u32;
+                for mut mana in player_query.iter_mut() {
+                    mana.current_mana = (mana.current_mana + refund).min(mana.max_mana);
+                }
+            }
+            SacrificeKind::Power => {
+                let position = transform.translation.truncate();
+                for (ally, ally_transform, team, health, mut stat_modifiers) in
+                    allies_query.iter_mut()
+                {
+                    let distance = (ally_transform.translation.truncate() - position).length();
+                    if ally == event.target
+                        || team.0 != Team::Evil
+                        || health.is_dead()
+                        || distance > SACRIFICE_BUFF_RADIUS
+                    {
+                        continue;
+                    }
+
+                    stat_modifiers.add(
+                        StatModifier::new(
+                            Stat::Damage,
+                            ModifierKind::Multiplicative(1.5),
+                            SACRIFICE_BUFF_SOURCE,
+                        )
+                        .with_duration(SACRIFICE_BUFF_DURATION),
+                    );
+                    stat_modifiers.add(
+                        StatModifier::new(
+                            Stat::Speed,
+                            ModifierKind::Multiplicative(1.2),
+                            SACRIFICE_BUFF_SOURCE,
+                        )
+                        .with_duration(SACRIFICE_BUFF_DURATION),
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,278 @@
+use bevy::prelude::*;
+
+use crate::ai::command::{CurrentCommand, UnitCommand};
+use crate::collision::Collider;
+use crate::cursor::CursorWorldPosition;
+use crate::units::health::Health;
+use crate::units::team::{CurrentTeam, Team};
+use crate::units::unit_types::UnitType;
+
+use super::plugin::Player;
+use super::summoning::SummonPlacement;
+
+// Anything shorter than this is a click rather than a box selection
+const DRAG_THRESHOLD: f32 = 8.0;
+const CLICK_SELECT_MARGIN: f32 = 16.0;
+const CLEAR_RALLY_POINT_DISTANCE: f32 = 48.0;
+
+#[derive(Component)]
+pub struct Selected;
+
+#[derive(Component)]
+pub struct SelectionHighlight;
+
+#[derive(Component)]
+pub struct SelectionBox;
+
+#[derive(Component)]
+pub struct RallyFlag;
+
+#[derive(Resource, Default)]
+pub struct SelectionDrag {
+    pub start: Option<Vec2>,
+}
+
+// Where newly summoned units head to, instead of staying on top of the player
+#[derive(Resource, Default)]
+pub struct DefaultRallyPoint(pub Option<Vec2>);
+
+pub fn setup(mut commands: Commands) {
+    commands.spawn((
+        SpriteBundle {
+            sprite: Sprite {
+                color: Color::rgba(0.6, 0.2, 0.9, 0.2),
+                custom_size: Some(Vec2::ONE),
+                ..default()
+            },
+            visibility: Visibility::Hidden,
+            ..default()
+        },
+        SelectionBox,
+    ));
+    commands.spawn((
+        SpriteBundle {
+            sprite: Sprite {
+                color: Color::rgba(0.6, 0.2, 0.9, 0.8),
+                custom_size: Some(Vec2::new(8.0, 32.0)),
+                ..default()
+            },
+            visibility: Visibility::Hidden,
+            ..default()
+        },
+        RallyFlag,
+    ));
+}
+
+fn is_selectable(team: &CurrentTeam, health: &Health) -> bool {
+    team.0 == Team::Evil && !health.is_dead()
+}
+
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn select_units(
+    mut commands: Commands,
+    mouse: Res<ButtonInput<MouseButton>>,
+    keys: Res<ButtonInput<KeyCode>>,
+    cursor_world_position: Res<CursorWorldPosition>,
+    placement: Res<SummonPlacement>,
+    mut drag: ResMut<SelectionDrag>,
+    query: Query<
+        (
+            Entity,
+            &Transform,
+            &Collider,
+            &CurrentTeam,
+            &Health,
+            Has<Selected>,
+        ),
+        (With<UnitType>, Without<Player>),
+    >,
+    mut box_query: Query<
+        (&mut Transform, &mut Visibility),
+        (With<SelectionBox>, Without<UnitType>),
+    >,
+) {
+    let Some(cursor_position) = cursor_world_position.0 else {
+        return;
+    };
+
+    // Left clicks place the summon while one is being placed
+    if placement.0.is_some() {
+        return;
+    }
+
+    if mouse.just_pressed(MouseButton::Left) {
+        drag.start = Some(cursor_position);
+    }
+
+    let Some(drag_start) = drag.start else {
+        return;
+    };
+
+    let min = drag_start.min(cursor_position);
+    let max = drag_start.max(cursor_position);
+    let is_box_selection = (max - min).length() > DRAG_THRESHOLD;
+
+    if let Ok((mut box_transform, mut box_visibility)) = box_query.get_single_mut() {
+        *box_visibility = if is_box_selection && mouse.pressed(MouseButton::Left) {
+            Visibility::Visible
+        } else {
+            Visibility::Hidden
+        };
+        box_transform.translation = ((min + max) * 0.5).extend(10.0);
+        box_transform.scale = (max - min).extend(1.0);
+    }
+
+    if !mouse.just_released(MouseButton::Left) {
+        return;
+    }
+
+    drag.start = None;
+
+    let newly_selected = if is_box_selection {
+        query
+            .iter()
+            .filter(|(_, transform, _, team, health, _)| {
+                let position = transform.translation.truncate();
+                let is_inside = position.cmpge(min).all() && position.cmple(max).all();
+                is_inside && is_selectable(team, health)
+            })
+            .map(|(entity, _, _, _, _, _)| entity)
+            .collect::<Vec<Entity>>()
+    } else {
+        query
+            .iter()
+            .filter(|(_, transform, collider, team, health, _)| {
+                is_selectable(team, health)
+                    && (transform.translation.truncate() - cursor_position).length()
+                        < collider.radius + CLICK_SELECT_MARGIN
+            })
+            .min_by(|a, b| {
+                let distance_to_a = (a.1.translation.truncate() - cursor_position).length();
+                let distance_to_b = (b.1.translation.truncate() - cursor_position).length();
+                distance_to_a.total_cmp(&distance_to_b)
+            })
+            .map(|(entity, _, _, _, _, _)| entity)
+            .into_iter()
+            .collect::<Vec<Entity>>()
+    };
+
+    // Holding shift adds to the current selection instead of replacing it
+    let is_adding = keys.pressed(KeyCode::ShiftLeft);
+    for (entity, _, _, _, _, is_selected) in query.iter() {
+        let is_newly_selected = newly_selected.contains(&entity);
+        if is_newly_selected && !is_selected {
+            commands.entity(entity).insert(Selected);
+        } else if !is_newly_selected && is_selected && !is_adding {
+            commands.entity(entity).remove::<Selected>();
+        }
+    }
+}
+
+pub fn deselect_dead_units(
+    mut commands: Commands,
+    query: Query<(Entity, &Health), With<Selected>>,
+) {
+    for (entity, health) in query.iter() {
+        if health.is_dead() {
+            commands.entity(entity).remove::<Selected>();
+        }
+    }
+}
+
+pub fn rally_selected_units(
+    mouse: Res<ButtonInput<MouseButton>>,
+    cursor_world_position: Res<CursorWorldPosition>,
+    placement: Res<SummonPlacement>,
+    mut default_rally_point: ResMut<DefaultRallyPoint>,
+    mut query: Query<&mut CurrentCommand, With<Selected>>,
+    player_query: Query<&Transform, With<Player>>,
+) {
+    // Right clicks cancel the summon while one is being placed
+    if !mouse.just_pressed(MouseButton::Right) || placement.0.is_some() {
+        return;
+    }
+
+    let Some(cursor_position) = cursor_world_position.0 else {
+        return;
+    };
+
+    if !query.is_empty() {
+        for mut current_command in query.iter_mut() {
+            current_command.0 = Some(UnitCommand::Rally(cursor_position));
+        }
+
+        return;
+    }
+
+    // Without a selection, right clicking sets where new summons go, and clicking the player clears it
+    let is_on_player = player_query.iter().next().is_some_and(|player_transform| {
+        (player_transform.translation.truncate() - cursor_position).length()
+            < CLEAR_RALLY_POINT_DISTANCE
+    });
+
+    default_rally_point.0 = if is_on_player {
+        None
+    } else {
+        Some(cursor_position)
+    };
+}
+
+pub fn update_rally_flag(
+    default_rally_point: Res<DefaultRallyPoint>,
+    mut query: Query<(&mut Transform, &mut Visibility), With<RallyFlag>>,
+) {
+    let Ok((mut transform, mut visibility)) = query.get_single_mut() else {
+        return;
+    };
+
+    match default_rally_point.0 {
+        Some(point) => {
+            transform.translation = point.extend(5.0);
+            *visibility = Visibility::Visible;
+        }
+        None => *visibility = Visibility::Hidden,
+    }
+}
+
+pub fn add_selection_highlights(
+    mut commands: Commands,
+    query: Query<(Entity, &Collider, &Transform), Added<Selected>>,
+) {
+    for (entity, collider, transform) in query.iter() {
+        // Children inherit the unit's scale, so undo it to keep the highlight matching the collider
+        let size = Vec2::splat(collider.radius * 2.0) / transform.scale.truncate();
+        commands.entity(entity).with_children(|parent| {
+            parent.spawn((
+                SpriteBundle {
+                    sprite: Sprite {
+                        color: Color::rgba(0.6, 0.2, 0.9, 0.35),
+                        custom_size: Some(size),
+                        ..default()
+                    },
+                    transform: Transform::from_xyz(0.0, 0.0, -0.1),
+                    ..default()
+                },
+                SelectionHighlight,
+            ));
+        });
+    }
+}
+
+pub fn remove_selection_highlights(
+    mut commands: Commands,
+    mut removed: RemovedComponents<Selected>,
+    children_query: Query<&Children>,
+    highlight_query: Query<Entity, With<SelectionHighlight>>,
+) {
+    for entity in removed.read() {
+        let Ok(children) = children_query.get(entity) else {
+            continue;
+        };
+
+        for child in children.iter() {
+            if highlight_query.get(*child).is_ok() {
+                commands.entity(*child).despawn_recursive();
+            }
+        }
+    }
+}
@@ -0,0 +1,318 @@
+use bevy::prelude::*;
+
+use crate::collision::Collider;
+use crate::combat::area::{get_distance_into_shape, AreaShape};
+use crate::combat::damage::{DamageEvent, DamageKind, HealEvent, Shield, UnitDamaged};
+use crate::combat::projectile::{spawn_projectile, ProjectileLaunch, ProjectileParams};
+use crate::combat::status::{ApplyStatusEffect, StatusEffect, StatusEffectKind, StatusEffects};
+use crate::cursor::CursorWorldPosition;
+use crate::gamestate::Cleanup;
+use crate::mana::Mana;
+use crate::units::health::Health;
+use crate::units::team::CurrentTeam;
+
+use super::plugin::Player;
+
+const DARK_BOLT_DAMAGE: u32 = 25;
+const FEAR_NOVA_RADIUS: f32 = 200.0;
+const FEAR_NOVA_DURATION: f32 = 3.0;
+const LIFE_DRAIN_LENGTH: f32 = 350.0;
+const LIFE_DRAIN_WIDTH: f32 = 24.0;
+const LIFE_DRAIN_DAMAGE: u32 = 20;
+const LIFE_DRAIN_HEAL_FRACTION: f32 = 0.5;
+const SHIELD_AMOUNT: u32 = 50;
+const SHIELD_DURATION: f32 = 6.0;
+
+const SPELL_VISUAL_DURATION: f32 = 0.3;
+const SPELL_COLOR: Color = Color::rgba(0.6, 0.2, 0.9, 0.8);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpellKind {
+    DarkBolt,  // A piercing projectile towards the cursor
+    FearNova,  // Scares every enemy around the player
+    LifeDrain, // A beam towards the cursor that heals the player for part of the damage
+    Shield,    // Absorbs damage for a while
+}
+
+#[derive(Debug, Clone)]
+pub struct Spell {
+    pub kind: SpellKind,
+    pub key: KeyCode,
+    pub mana_cost: u32,
+    pub cooldown_timer: Timer,
+}
+
+impl Spell {
+    fn new(kind: SpellKind, key: KeyCode, mana_cost: u32, cooldown: f32) -> Self {
+        let mut cooldown_timer = Timer::from_seconds(cooldown, TimerMode::Once);
+        // Everything is ready to be cast from the start
+        cooldown_timer.tick(cooldown_timer.duration());
+        Spell {
+            kind,
+            key,
+            mana_cost,
+            cooldown_timer,
+        }
+    }
+}
+
+#[derive(Component, Debug, Clone)]
+pub struct Spellbook(pub Vec<Spell>);
+
+impl Default for Spellbook {
+    fn default() -> Self {
+        Spellbook(vec![
+            Spell::new(SpellKind::DarkBolt, KeyCode::KeyQ, 15, 1.5),
+            Spell::new(SpellKind::FearNova, KeyCode::KeyE, 30, 12.0),
+            Spell::new(SpellKind::LifeDrain, KeyCode::KeyR, 20, 4.0),
+            Spell::new(SpellKind::Shield, KeyCode::KeyF, 25, 15.0),
+        ])
+    }
+}
+
+// Who the last life drain hit, the heal is based on what they actually took once it resolves
+#[derive(Component, Debug, Clone, Default)]
+pub struct LifeDrainTargets(pub Vec<Entity>);
+
+#[derive(Debug, Clone, Copy)]
+pub enum SpellVisualShape {
+    Beam { start: Vec2, end: Vec2 },
+    Ring { center: Vec2, radius: f32 },
+}
+
+#[derive(Component)]
+pub struct SpellVisual {
+    pub shape: SpellVisualShape,
+    pub timer: Timer,
+}
+
+fn spawn_spell_visual(commands: &mut Commands, shape: SpellVisualShape) {
+    commands.spawn((
+        SpellVisual {
+            shape,
+            timer: Timer::from_seconds(SPELL_VISUAL_DURATION, TimerMode::Once),
+        },
+        Cleanup,
+    ));
+}
+
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn system(
+    mut commands: Commands,
+    time: Res<Time>,
+    keys: Res<ButtonInput<KeyCode>>,
+    cursor_world_position: Res<CursorWorldPosition>,
+    mut query: Query<
+        (
+            Entity,
+            &mut Spellbook,
+            &mut LifeDrainTargets,
+            &mut Mana,
+            &Transform,
+            &CurrentTeam,
+            &Health,
+            &StatusEffects,
+        ),
+        With<Player>,
+    >,
+    enemies_query: Query<(Entity, &Transform, &Collider, &CurrentTeam, &Health), Without<Player>>,
+    mut damage_events: EventWriter<DamageEvent>,
+    mut status_events: EventWriter<ApplyStatusEffect>,
+) {
+    let Ok((
+        entity,
+        mut spellbook,
+        mut life_drain_targets,
+        mut mana,
+        transform,
+        team,
+        health,
+        status_effects,
+    )) = query.get_single_mut()
+    else {
+        return;
+    };
+
+    for spell in spellbook.0.iter_mut() {
+        spell.cooldown_timer.tick(time.delta());
+    }
+
+    if health.is_dead() || status_effects.has(StatusEffectKind::Stun) {
+        return;
+    }
+
+    let Some(cursor_position) = cursor_world_position.0 else {
+        return;
+    };
+
+    let position = transform.translation.truncate();
+    let aim_direction = (cursor_position - position)
+        .try_normalize()
+        .unwrap_or(Vec2::X);
+    let living_enemies = || {
+        enemies_query
+            .iter()
+            .filter(|(_, _, _, other_team, other_health)| {
+                !team.is_friendly(other_team) && !other_health.is_dead()
+            })
+    };
+
+    for spell in spellbook.0.iter_mut() {
+        if !keys.just_pressed(spell.key)
+            || !spell.cooldown_timer.finished()
+            || mana.current_mana < spell.mana_cost
+        {
+            continue;
+        }
+
+        match spell.kind {
+            SpellKind::DarkBolt => {
+                spawn_projectile(
+                    &mut commands,
+                    ProjectileLaunch {
+                        source: Some(entity),
+                        team: team.clone(),
+                        target: None,
+                        position,
+                        direction: aim_direction,
+                        damage: DARK_BOLT_DAMAGE,
+                    },
+                    &ProjectileParams {
+                        speed: 600.0,
+                        kind: DamageKind::Dark,
+                        pierce: 1,
+                        size: Vec2::new(20.0, 8.0),
+                        color: SPELL_COLOR,
+                        ..default()
+                    },
+                );
+            }
+            SpellKind::FearNova => {
+                for (enemy, enemy_transform, collider, _, _) in living_enemies() {
+                    let distance = (enemy_transform.translation.truncate() - position).length();
+                    if distance - collider.radius <= FEAR_NOVA_RADIUS {
+                        status_events.send(ApplyStatusEffect {
+                            source: Some(entity),
+                            target: enemy,
+                            effect: StatusEffect::fear(FEAR_NOVA_DURATION),
+                        });
+                    }
+                }
+
+                spawn_spell_visual(
+                    &mut commands,
+                    SpellVisualShape::Ring {
+                        center: position,
+                        radius: FEAR_NOVA_RADIUS,
+                    },
+                );
+            }
+            SpellKind::LifeDrain => {
+                let beam = AreaShape::Line {
+                    length: LIFE_DRAIN_LENGTH,
+                    width: LIFE_DRAIN_WIDTH,
+                };
+
+                for (enemy, enemy_transform, collider, _, _) in living_enemies() {
+                    let is_hit = get_distance_into_shape(
+                        beam,
+                        position,
+                        aim_direction,
+                        enemy_transform.translation.truncate(),
+                        collider.radius,
+                    )
+                    .is_some();
+                    if !is_hit {
+                        continue;
+                    }
+
+                    damage_events.send(DamageEvent {
+                        source: Some(entity),
+                        target: enemy,
+                        amount: LIFE_DRAIN_DAMAGE,
+                        kind: DamageKind::Dark,
+                    });
+                    life_drain_targets.0.push(enemy);
+                }
+
+                spawn_spell_visual(
+                    &mut commands,
+                    SpellVisualShape::Beam {
+                        start: position,
+                        end: position + aim_direction * LIFE_DRAIN_LENGTH,
+                    },
+                );
+            }
+            SpellKind::Shield => {
+                commands
+                    .entity(entity)
+                    .insert(Shield::new(SHIELD_AMOUNT, SHIELD_DURATION));
+            }
+        }
+
+        mana.current_mana -= spell.mana_cost;
+        spell.cooldown_timer.reset();
+    }
+}
+
+// Armor, resistances and invulnerability all count, the drain only heals for what got through
+pub fn heal_from_life_drain(
+    mut query: Query<(Entity, &mut LifeDrainTargets)>,
+    mut damaged_events: EventReader<UnitDamaged>,
+    mut heal_events: EventWriter<HealEvent>,
+) {
+    let events = damaged_events.read().collect::<Vec<&UnitDamaged>>();
+    for (entity, mut life_drain_targets) in query.iter_mut() {
+        if life_drain_targets.0.is_empty() {
+            continue;
+        }
+
+        let drained = events
+            .iter()
+            .filter(|event| {
+                event.source == Some(entity)
+                    && event.kind == DamageKind::Dark
+                    && life_drain_targets.0.contains(&event.target)
+            })
+            .map(|event| event.amount)
+            .sum::<u32>();
+        life_drain_targets.0.clear();
+
+        if drained > 0 {
+            heal_events.send(HealEvent {
+                source: Some(entity),
+                target: entity,
+                amount: (drained as f32 * LIFE_DRAIN_HEAL_FRACTION).round() as u32,
+            });
+        }
+    }
+}
+
+pub fn draw_spell_visuals(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut SpellVisual)>,
+    shield_query: Query<&Transform, (With<Player>, With<Shield>)>,
+    mut gizmos: Gizmos,
+) {
+    for (entity, mut spell_visual) in query.iter_mut() {
+        if spell_visual.timer.tick(time.delta()).finished() {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+
+        match spell_visual.shape {
+            SpellVisualShape::Beam { start, end } => {
+                gizmos.line_2d(start, end, SPELL_COLOR);
+            }
+            SpellVisualShape::Ring { center, radius } => {
+                // The ring grows out from the player over the visual's lifetime
+                gizmos.circle_2d(center, radius * spell_visual.timer.fraction(), SPELL_COLOR);
+            }
+        }
+    }
+
+    for transform in shield_query.iter() {
+        gizmos.circle_2d(transform.translation.truncate(), 56.0, SPELL_COLOR);
+    }
+}
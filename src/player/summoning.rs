@@ -1,96 +1,533 @@
+use crate::ai::behavior::{get_formation_slot, get_free_formation_slot, FollowBehavior};
+use crate::ai::command::{CurrentCommand, UnitCommand};
+use crate::animation::AnimatedChildSpawnParams;
+use crate::collision::{Collider, Obstacle};
+use crate::cursor::CursorWorldPosition;
+use crate::gamestate::GameMode;
 use crate::mana::Mana;
+use crate::player::movement::get_arena_bounds;
 use crate::player::plugin::Player;
-use crate::units::team::Team;
+use crate::player::selection::DefaultRallyPoint;
+use crate::structures::structure::{spawn_structure, CircleSpawn, StructureType};
+use crate::units::health::Health;
+use crate::units::team::{CurrentTeam, Team};
 use crate::units::unit_types::{
-    spawn_unit, Acolyte, Cat, Knight, UnitChildrenSpawnParamsFactory, UnitResource, UnitType,
-    Warrior,
+    spawn_unit, Acolyte, Cat, Mender, UnitBundle, UnitChildrenSpawnParamsFactory, UnitResource,
+    UnitType, Warlock, Warrior,
 };
 use bevy::ecs::system::EntityCommands;
 use bevy::prelude::*;
+use std::collections::HashMap;
 
+const SUMMON_RADIUS: f32 = 320.0;
+pub const POPULATION_CAP: u32 = 20;
+pub const UPKEEP_INTERVAL: f32 = 5.0;
+
+const GHOST_VALID_COLOR: Color = Color::rgba(0.7, 0.5, 1.0, 0.6);
+const GHOST_INVALID_COLOR: Color = Color::rgba(1.0, 0.2, 0.2, 0.6);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Placeable {
+    Unit(UnitType),
+    Structure(StructureType),
+}
+
+// What is waiting to be placed with the mouse, if anything
+#[derive(Resource, Default)]
+pub struct SummonPlacement(pub Option<Placeable>);
+
+#[derive(Component)]
+pub struct SummonGhost;
+
+// Time left before each unit type can be summoned again, missing means ready
+#[derive(Resource, Default)]
+pub struct SummonCooldowns(pub HashMap<UnitType, Timer>);
+
+impl SummonCooldowns {
+    pub fn remaining_secs(&self, unit: UnitType) -> f32 {
+        self.0
+            .get(&unit)
+            .map_or(0.0, |timer| timer.remaining_secs())
+    }
+}
+
+#[derive(Resource)]
+pub struct UpkeepTimer(pub Timer);
+
+impl Default for UpkeepTimer {
+    fn default() -> Self {
+        UpkeepTimer(Timer::from_seconds(UPKEEP_INTERVAL, TimerMode::Repeating))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlacementError {
+    OutsideArena,
+    OutOfRange,
+    OnObstacle,
+    NotEnoughMana,
+    OnCooldown,
+    PopulationCapped,
+}
+
+pub fn get_placement_binds() -> Vec<(KeyCode, Placeable)> {
+    // let column_staggered_colemak_binds = vec![
+    //     (KeyCode::KeyN, UnitType::Acolyte),
+    //     (KeyCode::KeyE, UnitType::Warrior),
+    //     (KeyCode::KeyI, UnitType::Cat),
+    // ];
+    vec![
+        (KeyCode::Digit1, Placeable::Unit(UnitType::Acolyte)),
+        (KeyCode::Digit2, Placeable::Unit(UnitType::Warrior)),
+        (KeyCode::Digit3, Placeable::Unit(UnitType::Cat)),
+        (KeyCode::Digit4, Placeable::Unit(UnitType::Warlock)),
+        (KeyCode::Digit5, Placeable::Unit(UnitType::Mender)),
+        (
+            KeyCode::Digit6,
+            Placeable::Structure(StructureType::SpikeTrap),
+        ),
+        (
+            KeyCode::Digit7,
+            Placeable::Structure(StructureType::Obelisk),
+        ),
+        (KeyCode::Digit8, Placeable::Structure(StructureType::Ward)),
+        (
+            KeyCode::Digit9,
+            Placeable::Structure(StructureType::SummoningCircle),
+        ),
+    ]
+}
+
+// The supply taken up by every living summon on the player's side
+pub fn get_used_supply<'a>(
+    unit_configs: &UnitResource,
+    units: impl IntoIterator<Item = (&'a UnitType, &'a CurrentTeam, &'a Health)>,
+) -> u32 {
+    units
+        .into_iter()
+        .filter(|(_, team, health)| team.0 == Team::Evil && !health.is_dead())
+        .filter_map(|(unit, _, _)| unit_configs.try_get(*unit))
+        .map(|config| config.supply)
+        .sum()
+}
+
+// The limits that only apply to units, on top of where they are placed
+fn validate_summon(
+    placeable: Placeable,
+    unit_configs: &UnitResource,
+    cooldowns: &SummonCooldowns,
+    used_supply: u32,
+) -> Result<(), PlacementError> {
+    let Placeable::Unit(unit) = placeable else {
+        return Ok(());
+    };
+
+    if cooldowns.remaining_secs(unit) > 0.0 {
+        return Err(PlacementError::OnCooldown);
+    }
+
+    let supply = unit_configs.try_get(unit).map_or(0, |config| config.supply);
+    if used_supply + supply > POPULATION_CAP {
+        return Err(PlacementError::PopulationCapped);
+    }
+
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn system(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
     keys: Res<ButtonInput<KeyCode>>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    cursor_world_position: Res<CursorWorldPosition>,
     unit_configs: Res<UnitResource>,
+    default_rally_point: Res<DefaultRallyPoint>,
+    time: Res<Time>,
+    mut placement: ResMut<SummonPlacement>,
+    mut cooldowns: ResMut<SummonCooldowns>,
     mut query: Query<(&mut Mana, &Transform), With<Player>>,
+    followers_query: Query<(&FollowBehavior, &Health), Without<CircleSpawn>>,
+    units_query: Query<(&UnitType, &CurrentTeam, &Health)>,
+    obstacle_query: Query<(&Transform, &Collider), With<Obstacle>>,
+    window_query: Query<&Window>,
 ) {
-    // let column_staggered_colemak_binds = vec![
-    //     (KeyCode::KeyN, UnitType::Acolyte),
-    //     (KeyCode::KeyE, UnitType::Warrior),
-    //     (KeyCode::KeyI, UnitType::Cat),
-    // ];
-    // let pressed_units = handle_input(&keys, &column_staggered_colemak_binds);
-
-    let row_staggered_qwerty_binds = vec![
-        (KeyCode::Digit1, UnitType::Acolyte),
-        (KeyCode::Digit2, UnitType::Warrior),
-        (KeyCode::Digit3, UnitType::Cat),
-    ];
-    let pressed_units = handle_input(&keys, &row_staggered_qwerty_binds);
-
-    pressed_units.into_iter().for_each(|(_, unit)| {
-        let (mut mana, transform) = query.single_mut();
-        let unit_cost = unit_configs.get(*unit).cost;
-        if mana.current_mana < unit_cost {
-            return;
+    for timer in cooldowns.0.values_mut() {
+        timer.tick(time.delta());
+    }
+
+    let row_staggered_qwerty_binds = get_placement_binds();
+    if let Some((_, placeable)) = handle_input(&keys, &row_staggered_qwerty_binds).last() {
+        placement.0 = Some(*placeable);
+    }
+
+    let Some(placeable) = placement.0 else {
+        return;
+    };
+
+    if mouse.just_pressed(MouseButton::Right) || keys.just_pressed(KeyCode::Escape) {
+        placement.0 = None;
+        return;
+    }
+
+    if !mouse.just_pressed(MouseButton::Left) {
+        return;
+    }
+
+    let Some(cursor_position) = cursor_world_position.0 else {
+        return;
+    };
+
+    // Only the player's own units have a summon config, anything else can't be placed
+    let (Some(cost), Some(radius)) = (get_cost(placeable, &unit_configs), get_radius(placeable))
+    else {
+        placement.0 = None;
+        return;
+    };
+
+    let (mut mana, player_transform) = query.single_mut();
+    let placement_result = validate_placement(
+        radius,
+        cursor_position,
+        player_transform,
+        &mana,
+        cost,
+        obstacle_query.iter(),
+        window_query.single(),
+    );
+
+    let summon_result = validate_summon(
+        placeable,
+        &unit_configs,
+        &cooldowns,
+        get_used_supply(&unit_configs, units_query.iter()),
+    );
+
+    if placement_result.is_err() || summon_result.is_err() {
+        return;
+    }
+
+    match placeable {
+        Placeable::Structure(structure_type) => {
+            spawn_structure(&mut commands, structure_type, Team::Evil, cursor_position);
         }
+        Placeable::Unit(unit) => {
+            let slot_index = get_free_formation_slot(
+                followers_query
+                    .iter()
+                    .filter(|(_, health)| !health.is_dead())
+                    .map(|(follow_behavior, _)| follow_behavior.slot_index),
+            );
+            let create_follow_behavior = || FollowBehavior {
+                slot_index,
+                slot_offset: get_formation_slot(slot_index),
+                ..default()
+            };
 
-        match unit {
-            UnitType::Acolyte => summon_unit(
-                &mut commands,
-                &asset_server,
-                &mut texture_atlas_layouts,
-                Acolyte::default(),
-                transform,
-            )
-            .insert(Acolyte::default()),
-            UnitType::Warrior => summon_unit(
-                &mut commands,
-                &asset_server,
-                &mut texture_atlas_layouts,
-                Warrior,
-                transform,
-            )
-            .insert(Warrior),
-            UnitType::Cat => summon_unit(
-                &mut commands,
-                &asset_server,
-                &mut texture_atlas_layouts,
-                Cat,
-                transform,
-            )
-            .insert(Cat),
-            UnitType::Knight => summon_unit(
-                &mut commands,
-                &asset_server,
-                &mut texture_atlas_layouts,
-                Knight,
-                transform,
-            )
-            .insert(Knight),
-        };
+            let rally_command = CurrentCommand(default_rally_point.0.map(UnitCommand::Rally));
 
-        mana.current_mana -= unit_cost;
-    });
+            match unit {
+                UnitType::Acolyte => summon_unit(
+                    &mut commands,
+                    &asset_server,
+                    &mut texture_atlas_layouts,
+                    Acolyte::default(),
+                    cursor_position,
+                )
+                .insert((Acolyte::default(), rally_command)),
+                UnitType::Warrior => summon_unit(
+                    &mut commands,
+                    &asset_server,
+                    &mut texture_atlas_layouts,
+                    Warrior,
+                    cursor_position,
+                )
+                .insert((Warrior, create_follow_behavior(), rally_command)),
+                UnitType::Cat => summon_unit(
+                    &mut commands,
+                    &asset_server,
+                    &mut texture_atlas_layouts,
+                    Cat,
+                    cursor_position,
+                )
+                .insert((
+                    Cat,
+                    Cat::sweep_attack(),
+                    create_follow_behavior(),
+                    rally_command,
+                )),
+                UnitType::Warlock => summon_unit(
+                    &mut commands,
+                    &asset_server,
+                    &mut texture_atlas_layouts,
+                    Warlock,
+                    cursor_position,
+                )
+                .insert((Warlock, create_follow_behavior(), rally_command)),
+                UnitType::Mender => summon_unit(
+                    &mut commands,
+                    &asset_server,
+                    &mut texture_atlas_layouts,
+                    Mender,
+                    cursor_position,
+                )
+                .insert((Mender, create_follow_behavior(), rally_command)),
+                // Only the player's own units have a summon config
+                _ => return,
+            };
+
+            if let Some(config) = unit_configs.try_get(unit) {
+                cooldowns
+                    .0
+                    .insert(unit, Timer::from_seconds(config.cooldown, TimerMode::Once));
+            }
+        }
+    }
+
+    mana.current_mana -= cost;
+
+    // Holding shift keeps placing the same thing
+    if !keys.pressed(KeyCode::ShiftLeft) {
+        placement.0 = None;
+    }
 }
 
 fn handle_input<'a>(
     keys: &'a Res<ButtonInput<KeyCode>>,
-    binds: &'a [(KeyCode, UnitType)],
-) -> impl Iterator<Item = &'a (KeyCode, UnitType)> + 'a {
+    binds: &'a [(KeyCode, Placeable)],
+) -> impl Iterator<Item = &'a (KeyCode, Placeable)> + 'a {
     binds
         .iter()
         .filter(move |(key, _unit)| keys.just_pressed(*key))
 }
 
+fn get_unit_bundle(unit: UnitType) -> Option<UnitBundle> {
+    match unit {
+        UnitType::Acolyte => Some(Acolyte::default().create_unit_bundle()),
+        UnitType::Warrior => Some(Warrior.create_unit_bundle()),
+        UnitType::Cat => Some(Cat.create_unit_bundle()),
+        UnitType::Warlock => Some(Warlock.create_unit_bundle()),
+        UnitType::Mender => Some(Mender.create_unit_bundle()),
+        _ => None,
+    }
+}
+
+fn get_cost(placeable: Placeable, unit_configs: &UnitResource) -> Option<u32> {
+    match placeable {
+        Placeable::Unit(unit) => unit_configs.try_get(unit).map(|config| config.cost),
+        Placeable::Structure(structure_type) => Some(structure_type.config().cost),
+    }
+}
+
+fn get_radius(placeable: Placeable) -> Option<f32> {
+    match placeable {
+        Placeable::Unit(unit) => get_unit_bundle(unit).map(|bundle| bundle.collider.radius),
+        Placeable::Structure(structure_type) => Some(structure_type.config().radius),
+    }
+}
+
+fn get_children_spawn_params(unit: UnitType) -> Vec<AnimatedChildSpawnParams> {
+    match unit {
+        UnitType::Acolyte => Acolyte::default().create_children_spawn_params(),
+        UnitType::Warrior => Warrior.create_children_spawn_params(),
+        UnitType::Cat => Cat.create_children_spawn_params(),
+        UnitType::Warlock => Warlock.create_children_spawn_params(),
+        UnitType::Mender => Mender.create_children_spawn_params(),
+        _ => Vec::new(),
+    }
+}
+
+pub fn validate_placement<'a>(
+    radius: f32,
+    position: Vec2,
+    player_transform: &Transform,
+    mana: &Mana,
+    cost: u32,
+    obstacles: impl IntoIterator<Item = (&'a Transform, &'a Collider)>,
+    window: &Window,
+) -> Result<(), PlacementError> {
+    let arena_bounds = get_arena_bounds(window);
+    if position.abs().cmpgt(arena_bounds).any() {
+        return Err(PlacementError::OutsideArena);
+    }
+
+    if (position - player_transform.translation.truncate()).length() > SUMMON_RADIUS {
+        return Err(PlacementError::OutOfRange);
+    }
+
+    let is_on_obstacle = obstacles
+        .into_iter()
+        .any(|(obstacle_transform, obstacle_collider)| {
+            (obstacle_transform.translation.truncate() - position).length()
+                < obstacle_collider.radius + radius
+        });
+    if is_on_obstacle {
+        return Err(PlacementError::OnObstacle);
+    }
+
+    if mana.current_mana < cost {
+        return Err(PlacementError::NotEnoughMana);
+    }
+
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn update_ghost(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+    placement: Res<SummonPlacement>,
+    cursor_world_position: Res<CursorWorldPosition>,
+    unit_configs: Res<UnitResource>,
+    cooldowns: Res<SummonCooldowns>,
+    mut ghost_query: Query<
+        (Entity, &mut Transform, &mut Sprite, &mut Visibility),
+        With<SummonGhost>,
+    >,
+    player_query: Query<(&Mana, &Transform), (With<Player>, Without<SummonGhost>)>,
+    obstacle_query: Query<(&Transform, &Collider), (With<Obstacle>, Without<SummonGhost>)>,
+    units_query: Query<(&UnitType, &CurrentTeam, &Health)>,
+    window_query: Query<&Window>,
+    mut gizmos: Gizmos,
+) {
+    if placement.is_changed() {
+        for (entity, _, _, _) in ghost_query.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+
+        let Some(placeable) = placement.0 else {
+            return;
+        };
+
+        let unit = match placeable {
+            Placeable::Unit(unit) => unit,
+            Placeable::Structure(structure_type) => {
+                let config = structure_type.config();
+                commands.spawn((
+                    SpriteBundle {
+                        sprite: Sprite {
+                            color: GHOST_VALID_COLOR,
+                            custom_size: Some(Vec2::splat(config.radius * 2.0)),
+                            ..default()
+                        },
+                        visibility: Visibility::Hidden,
+                        ..default()
+                    },
+                    SummonGhost,
+                ));
+
+                return;
+            }
+        };
+
+        // The first frame of the idle animation is a good enough preview
+        let (Some(bundle), Some(idle_params)) = (
+            get_unit_bundle(unit),
+            get_children_spawn_params(unit).into_iter().next(),
+        ) else {
+            return;
+        };
+
+        let layout = TextureAtlasLayout::from_grid(
+            idle_params.tile_size,
+            idle_params.grid.0,
+            idle_params.grid.1,
+            None,
+            None,
+        );
+        commands.spawn((
+            SpriteSheetBundle {
+                texture: asset_server.load(idle_params.texture_path),
+                atlas: TextureAtlas {
+                    layout: texture_atlas_layouts.add(layout),
+                    index: 0,
+                },
+                sprite: Sprite {
+                    color: GHOST_VALID_COLOR,
+                    ..default()
+                },
+                transform: Transform::from_scale(bundle.transform.scale),
+                visibility: Visibility::Hidden,
+                ..default()
+            },
+            SummonGhost,
+        ));
+
+        return;
+    }
+
+    let (Some(placeable), Some(cursor_position)) = (placement.0, cursor_world_position.0) else {
+        return;
+    };
+
+    let Some((mana, player_transform)) = player_query.iter().next() else {
+        return;
+    };
+
+    let (Some(cost), Some(radius)) = (get_cost(placeable, &unit_configs), get_radius(placeable))
+    else {
+        return;
+    };
+
+    let placement_result = validate_placement(
+        radius,
+        cursor_position,
+        player_transform,
+        mana,
+        cost,
+        obstacle_query.iter(),
+        window_query.single(),
+    )
+    .and_then(|_| {
+        validate_summon(
+            placeable,
+            &unit_configs,
+            &cooldowns,
+            get_used_supply(&unit_configs, units_query.iter()),
+        )
+    });
+
+    let ghost_color = if placement_result.is_ok() {
+        GHOST_VALID_COLOR
+    } else {
+        GHOST_INVALID_COLOR
+    };
+
+    for (_, mut transform, mut sprite, mut visibility) in ghost_query.iter_mut() {
+        transform.translation = cursor_position.extend(50.0);
+        sprite.color = ghost_color;
+        *visibility = Visibility::Visible;
+    }
+
+    gizmos.circle_2d(
+        player_transform.translation.truncate(),
+        SUMMON_RADIUS,
+        Color::rgba(0.6, 0.2, 0.9, 0.5),
+    );
+
+    if placement_result.is_err() {
+        let cross_size = 16.0;
+        gizmos.line_2d(
+            cursor_position - Vec2::splat(cross_size),
+            cursor_position + Vec2::splat(cross_size),
+            GHOST_INVALID_COLOR,
+        );
+        gizmos.line_2d(
+            cursor_position + Vec2::new(-cross_size, cross_size),
+            cursor_position + Vec2::new(cross_size, -cross_size),
+            GHOST_INVALID_COLOR,
+        );
+    }
+}
+
 fn summon_unit<'a>(
     commands: &'a mut Commands,
     asset_server: &'a Res<AssetServer>,
     texture_atlas_layouts: &'a mut ResMut<Assets<TextureAtlasLayout>>,
     unit_component: impl UnitChildrenSpawnParamsFactory + Clone,
-    player_transform: &'a Transform,
+    position: Vec2,
 ) -> EntityCommands<'a> {
     spawn_unit(
         commands,
@@ -98,6 +535,37 @@ fn summon_unit<'a>(
         texture_atlas_layouts,
         unit_component,
         Team::Evil,
-        player_transform.translation.truncate(),
+        position,
     )
 }
+
+pub fn drain_upkeep(
+    time: Res<Time>,
+    game_mode: Res<GameMode>,
+    unit_configs: Res<UnitResource>,
+    mut upkeep_timer: ResMut<UpkeepTimer>,
+    units_query: Query<(&UnitType, &CurrentTeam, &Health)>,
+    mut player_query: Query<&mut Mana, With<Player>>,
+) {
+    if !game_mode.summon_upkeep || !upkeep_timer.0.tick(time.delta()).just_finished() {
+        return;
+    }
+
+    let upkeep = get_upkeep(&unit_configs, units_query.iter());
+    for mut mana in player_query.iter_mut() {
+        mana.current_mana = mana.current_mana.saturating_sub(upkeep);
+    }
+}
+
+// The mana drained every upkeep interval by every living summon on the player's side
+pub fn get_upkeep<'a>(
+    unit_configs: &UnitResource,
+    units: impl IntoIterator<Item = (&'a UnitType, &'a CurrentTeam, &'a Health)>,
+) -> u32 {
+    units
+        .into_iter()
+        .filter(|(_, team, health)| team.0 == Team::Evil && !health.is_dead())
+        .filter_map(|(unit, _, _)| unit_configs.try_get(*unit))
+        .map(|config| config.upkeep)
+        .sum()
+}
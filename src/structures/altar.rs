@@ -0,0 +1,55 @@
+use bevy::prelude::*;
+
+use crate::collision::{Collider, Obstacle};
+use crate::gamestate::Cleanup;
+use crate::units::health::Health;
+use crate::units::team::{CurrentTeam, Team};
+
+const ALTAR_HEALTH: u32 = 500;
+const ALTAR_RADIUS: f32 = 48.0;
+const ALTAR_COLOR: Color = Color::rgb(0.45, 0.1, 0.55);
+const ALTAR_RUINED_COLOR: Color = Color::rgb(0.2, 0.2, 0.2);
+
+// What the player is defending, the enemies march towards it and the run is lost when it falls
+#[derive(Component)]
+pub struct Altar;
+
+pub fn spawn_altar(commands: &mut Commands) {
+    commands.spawn((
+        SpriteBundle {
+            sprite: Sprite {
+                color: ALTAR_COLOR,
+                custom_size: Some(Vec2::splat(ALTAR_RADIUS * 2.0)),
+                ..default()
+            },
+            transform: Transform::from_translation(Vec3::new(0.0, 0.0, 1.0)),
+            ..default()
+        },
+        Altar,
+        Obstacle,
+        Collider {
+            radius: ALTAR_RADIUS,
+        },
+        Health::new(ALTAR_HEALTH),
+        CurrentTeam(Team::Evil),
+        Cleanup,
+    ));
+}
+
+// The altar crumbles towards grey as it takes damage
+#[allow(clippy::type_complexity)]
+pub fn update_altar_color(
+    mut query: Query<(&Health, &mut Sprite), (With<Altar>, Changed<Health>)>,
+) {
+    for (health, mut sprite) in query.iter_mut() {
+        let fraction = health.fraction();
+        let [r, g, b, a] = ALTAR_RUINED_COLOR.as_rgba_f32();
+        let [to_r, to_g, to_b, _] = ALTAR_COLOR.as_rgba_f32();
+        sprite.color = Color::rgba(
+            r + (to_r - r) * fraction,
+            g + (to_g - g) * fraction,
+            b + (to_b - b) * fraction,
+            a,
+        );
+    }
+}
@@ -0,0 +1,23 @@
+use bevy::prelude::*;
+
+use crate::combat::damage;
+use crate::structures::{altar, structure};
+
+pub struct StructurePlugin;
+
+impl Plugin for StructurePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                altar::update_altar_color,
+                structure::update_spike_traps.before(damage::resolve_damage),
+                structure::update_turrets,
+                structure::update_slow_auras,
+                structure::update_unit_spawners,
+                structure::destroy_dead_structures.after(damage::resolve_damage),
+                structure::draw_structure_ranges,
+            ),
+        );
+    }
+}
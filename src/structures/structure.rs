@@ -0,0 +1,418 @@
+use bevy::prelude::*;
+
+use crate::ai::behavior::{
+    get_formation_slot, get_free_formation_slot, FollowBehavior, GuardBehavior,
+};
+use crate::collision::{Anchored, Collider, Obstacle};
+use crate::combat::damage::{DamageEvent, DamageKind};
+use crate::combat::projectile::{spawn_projectile, ProjectileLaunch, ProjectileParams};
+use crate::combat::status::{ApplyStatusEffect, StatusEffect};
+use crate::gamestate::Cleanup;
+use crate::player::summoning::{get_used_supply, POPULATION_CAP};
+use crate::units::health::Health;
+use crate::units::team::{CurrentTeam, Team};
+use crate::units::unit_types::{spawn_unit, UnitResource, UnitType, Warrior};
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StructureType {
+    SpikeTrap,
+    Obelisk,
+    Ward,
+    SummoningCircle,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct StructureConfig {
+    pub cost: u32,
+    pub health: u32,
+    pub radius: f32,
+    pub color: Color,
+    pub is_blocking: bool, // Units have to walk around it, otherwise they walk right over it
+}
+
+impl StructureType {
+    pub fn config(&self) -> StructureConfig {
+        match self {
+            StructureType::SpikeTrap => StructureConfig {
+                cost: 15,
+                health: 60,
+                radius: 24.0,
+                color: Color::rgb(0.85, 0.8, 0.7),
+                is_blocking: false,
+            },
+            StructureType::Obelisk => StructureConfig {
+                cost: 45,
+                health: 250,
+                radius: 28.0,
+                color: Color::rgb(0.25, 0.1, 0.35),
+                is_blocking: true,
+            },
+            StructureType::Ward => StructureConfig {
+                cost: 25,
+                health: 100,
+                radius: 16.0,
+                color: Color::rgb(0.3, 0.5, 0.9),
+                is_blocking: false,
+            },
+            StructureType::SummoningCircle => StructureConfig {
+                cost: 60,
+                health: 200,
+                radius: 40.0,
+                color: Color::rgba(0.6, 0.1, 0.2, 0.8),
+                is_blocking: false,
+            },
+        }
+    }
+}
+
+// Hurts every enemy standing on it, then needs a moment to reset
+#[derive(Component, Clone, Debug)]
+pub struct SpikeTrap {
+    pub damage: u32,
+    pub cooldown_timer: Timer,
+}
+
+// Shoots at the nearest enemy in range
+#[derive(Component, Clone, Debug)]
+pub struct Turret {
+    pub range: f32,
+    pub damage: u32,
+    pub projectile: ProjectileParams,
+    pub cooldown_timer: Timer,
+}
+
+// Keeps every enemy within the radius slowed
+#[derive(Component, Clone, Debug)]
+pub struct SlowAura {
+    pub radius: f32,
+    pub magnitude: f32,
+    pub pulse_timer: Timer,
+}
+
+// Calls in a unit every now and then, as long as it hasn't reached its limit
+#[derive(Component, Clone, Debug)]
+pub struct UnitSpawner {
+    pub max_alive: usize,
+    pub spawned: Vec<Entity>,
+    pub timer: Timer,
+}
+
+// Called in by a summoning circle, it holds a slot around the circle rather than the player
+#[derive(Component, Clone, Copy, Debug)]
+pub struct CircleSpawn;
+
+const SLOW_AURA_PULSE: f32 = 0.5;
+
+pub fn spawn_structure(
+    commands: &mut Commands,
+    structure_type: StructureType,
+    team: Team,
+    position: Vec2,
+) -> Entity {
+    let config = structure_type.config();
+    let mut entity = commands.spawn((
+        SpriteBundle {
+            sprite: Sprite {
+                color: config.color,
+                custom_size: Some(Vec2::splat(config.radius * 2.0)),
+                ..default()
+            },
+            transform: Transform::from_translation(position.extend(1.0)),
+            ..default()
+        },
+        structure_type,
+        Collider {
+            radius: config.radius,
+        },
+        Health::new(config.health),
+        CurrentTeam(team),
+        Cleanup,
+    ));
+
+    if config.is_blocking {
+        entity.insert(Obstacle);
+    } else {
+        entity.insert(Anchored);
+    }
+
+    match structure_type {
+        StructureType::SpikeTrap => entity.insert(SpikeTrap {
+            damage: 15,
+            cooldown_timer: Timer::from_seconds(1.0, TimerMode::Once),
+        }),
+        StructureType::Obelisk => entity.insert(Turret {
+            range: 320.0,
+            damage: 10,
+            projectile: ProjectileParams {
+                speed: 450.0,
+                kind: DamageKind::Dark,
+                is_homing: true,
+                color: Color::rgb(0.6, 0.2, 0.9),
+                ..default()
+            },
+            cooldown_timer: Timer::from_seconds(1.5, TimerMode::Once),
+        }),
+        StructureType::Ward => entity.insert(SlowAura {
+            radius: 180.0,
+            magnitude: 0.4,
+            pulse_timer: Timer::from_seconds(SLOW_AURA_PULSE, TimerMode::Repeating),
+        }),
+        StructureType::SummoningCircle => entity.insert(UnitSpawner {
+            max_alive: 2,
+            spawned: Vec::new(),
+            timer: Timer::from_seconds(12.0, TimerMode::Repeating),
+        }),
+    };
+
+    entity.id()
+}
+
+fn is_enemy_in_reach(
+    team: &CurrentTeam,
+    position: Vec2,
+    reach: f32,
+    other_team: &CurrentTeam,
+    other_health: &Health,
+    other_transform: &Transform,
+    other_collider: &Collider,
+) -> bool {
+    !team.is_friendly(other_team)
+        && !other_health.is_dead()
+        && (other_transform.translation.truncate() - position).length()
+            <= reach + other_collider.radius
+}
+
+pub fn update_spike_traps(
+    time: Res<Time>,
+    mut query: Query<(
+        Entity,
+        &mut SpikeTrap,
+        &Transform,
+        &Collider,
+        &CurrentTeam,
+        &Health,
+    )>,
+    others_query: Query<(Entity, &Transform, &Collider, &CurrentTeam, &Health), Without<SpikeTrap>>,
+    mut damage_events: EventWriter<DamageEvent>,
+) {
+    for (entity, mut spike_trap, transform, collider, team, health) in query.iter_mut() {
+        if health.is_dead() || !spike_trap.cooldown_timer.tick(time.delta()).finished() {
+            continue;
+        }
+
+        let position = transform.translation.truncate();
+        let mut has_triggered = false;
+        for (other, other_transform, other_collider, other_team, other_health) in
+            others_query.iter()
+        {
+            if !is_enemy_in_reach(
+                team,
+                position,
+                collider.radius,
+                other_team,
+                other_health,
+                other_transform,
+                other_collider,
+            ) {
+                continue;
+            }
+
+            damage_events.send(DamageEvent {
+                source: Some(entity),
+                target: other,
+                amount: spike_trap.damage,
+                kind: DamageKind::Physical,
+            });
+            has_triggered = true;
+        }
+
+        if has_triggered {
+            spike_trap.cooldown_timer.reset();
+        }
+    }
+}
+
+pub fn update_turrets(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut Turret, &Transform, &CurrentTeam, &Health)>,
+    others_query: Query<(Entity, &Transform, &Collider, &CurrentTeam, &Health), Without<Turret>>,
+) {
+    for (entity, mut turret, transform, team, health) in query.iter_mut() {
+        if health.is_dead() || !turret.cooldown_timer.tick(time.delta()).finished() {
+            continue;
+        }
+
+        let position = transform.translation.truncate();
+        let nearest = others_query
+            .iter()
+            .filter(
+                |(_, other_transform, other_collider, other_team, other_health)| {
+                    is_enemy_in_reach(
+                        team,
+                        position,
+                        turret.range,
+                        other_team,
+                        other_health,
+                        other_transform,
+                        other_collider,
+                    )
+                },
+            )
+            .map(|(other, other_transform, _, _, _)| {
+                (other, other_transform.translation.truncate() - position)
+            })
+            .min_by(|a, b| a.1.length().total_cmp(&b.1.length()));
+
+        let Some((target, offset)) = nearest else {
+            continue;
+        };
+
+        spawn_projectile(
+            &mut commands,
+            ProjectileLaunch {
+                source: Some(entity),
+                team: team.clone(),
+                target: Some(target),
+                position,
+                direction: offset,
+                damage: turret.damage,
+            },
+            &turret.projectile,
+        );
+        turret.cooldown_timer.reset();
+    }
+}
+
+pub fn update_slow_auras(
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut SlowAura, &Transform, &CurrentTeam, &Health)>,
+    others_query: Query<(Entity, &Transform, &Collider, &CurrentTeam, &Health), Without<SlowAura>>,
+    mut status_events: EventWriter<ApplyStatusEffect>,
+) {
+    for (entity, mut slow_aura, transform, team, health) in query.iter_mut() {
+        if health.is_dead() || !slow_aura.pulse_timer.tick(time.delta()).just_finished() {
+            continue;
+        }
+
+        let position = transform.translation.truncate();
+        for (other, other_transform, other_collider, other_team, other_health) in
+            others_query.iter()
+        {
+            if !is_enemy_in_reach(
+                team,
+                position,
+                slow_aura.radius,
+                other_team,
+                other_health,
+                other_transform,
+                other_collider,
+            ) {
+                continue;
+            }
+
+            // Lasts a little longer than a pulse so the slow doesn't flicker off between pulses
+            status_events.send(ApplyStatusEffect {
+                source: Some(entity),
+                target: other,
+                effect: StatusEffect::slow(slow_aura.magnitude, SLOW_AURA_PULSE * 2.0),
+            });
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn update_unit_spawners(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+    time: Res<Time>,
+    unit_configs: Res<UnitResource>,
+    mut query: Query<(&mut UnitSpawner, &Transform, &CurrentTeam, &Health)>,
+    spawned_query: Query<(&Health, &FollowBehavior), Without<UnitSpawner>>,
+    units_query: Query<(&UnitType, &CurrentTeam, &Health)>,
+) {
+    // Circle spawns take up supply like any summon, so they respect the population cap too
+    let mut used_supply = get_used_supply(&unit_configs, units_query.iter());
+    let supply = unit_configs.get(UnitType::Warrior).supply;
+    for (mut unit_spawner, transform, team, health) in query.iter_mut() {
+        if health.is_dead() || !unit_spawner.timer.tick(time.delta()).just_finished() {
+            continue;
+        }
+
+        unit_spawner.spawned.retain(|spawned| {
+            spawned_query
+                .get(*spawned)
+                .is_ok_and(|(spawned_health, _)| !spawned_health.is_dead())
+        });
+        if unit_spawner.spawned.len() >= unit_spawner.max_alive {
+            continue;
+        }
+
+        if team.0 == Team::Evil && used_supply + supply > POPULATION_CAP {
+            continue;
+        }
+
+        // Spawns guard the circle from their own slot around it, rather than joining the player
+        let slot_index = get_free_formation_slot(
+            spawned_query
+                .iter_many(&unit_spawner.spawned)
+                .map(|(_, follow_behavior)| follow_behavior.slot_index),
+        );
+        let position = transform.translation.truncate();
+        let spawned = spawn_unit(
+            &mut commands,
+            &asset_server,
+            &mut texture_atlas_layouts,
+            Warrior,
+            team.0.clone(),
+            position,
+        )
+        .insert((
+            Warrior,
+            CircleSpawn,
+            FollowBehavior {
+                slot_index,
+                slot_offset: get_formation_slot(slot_index),
+                ..default()
+            },
+            GuardBehavior {
+                point: Some(position),
+            },
+        ))
+        .id();
+        unit_spawner.spawned.push(spawned);
+        if team.0 == Team::Evil {
+            used_supply += supply;
+        }
+    }
+}
+
+// Structures have no death animation, they are simply gone when destroyed
+pub fn destroy_dead_structures(
+    mut commands: Commands,
+    query: Query<(Entity, &Health), With<StructureType>>,
+) {
+    for (entity, health) in query.iter() {
+        if health.is_dead() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn draw_structure_ranges(
+    query: Query<(&Transform, Option<&Turret>, Option<&SlowAura>), With<StructureType>>,
+    mut gizmos: Gizmos,
+) {
+    for (transform, turret, slow_aura) in query.iter() {
+        let position = transform.translation.truncate();
+        if let Some(slow_aura) = slow_aura {
+            gizmos.circle_2d(position, slow_aura.radius, Color::rgba(0.3, 0.5, 0.9, 0.3));
+        }
+
+        if let Some(turret) = turret {
+            gizmos.circle_2d(position, turret.range, Color::rgba(0.6, 0.2, 0.9, 0.15));
+        }
+    }
+}
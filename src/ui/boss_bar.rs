@@ -0,0 +1,30 @@
+use bevy::prelude::*;
+
+use crate::enemies::boss::Boss;
+use crate::units::health::Health;
+
+use super::plugin::{BossBar, BossBarFill, BossText};
+
+// Only the first living boss is shown, there is rarely more than one around
+pub fn update_boss_bar(
+    boss_query: Query<(&Boss, &Health)>,
+    mut bar_query: Query<&mut Visibility, With<BossBar>>,
+    mut fill_query: Query<&mut Transform, With<BossBarFill>>,
+    mut text_query: Query<&mut Text, With<BossText>>,
+) {
+    let boss = boss_query.iter().find(|(_, health)| !health.is_dead());
+    let mut visibility = bar_query.single_mut();
+    let Some((boss, health)) = boss else {
+        *visibility = Visibility::Hidden;
+        return;
+    };
+
+    *visibility = Visibility::Visible;
+    fill_query.single_mut().scale.x = health.fraction();
+    text_query.single_mut().sections[0].value = format!(
+        "{} - Phase {}/{}",
+        boss.kind.name(),
+        boss.phase + 1,
+        boss.phases.len()
+    );
+}
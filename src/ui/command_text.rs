@@ -0,0 +1,22 @@
+use bevy::prelude::*;
+
+use crate::player::commands::CommandScope;
+use crate::player::selection::Selected;
+
+use super::plugin::CommandText;
+
+pub fn update_command_text(
+    scope: Res<CommandScope>,
+    selected_query: Query<(), With<Selected>>,
+    mut text_query: Query<&mut Text, With<CommandText>>,
+) {
+    let selected_count = selected_query.iter().count();
+    let scope_name = match *scope {
+        _ if selected_count > 0 => format!("Selected ({})", selected_count),
+        CommandScope::All => "All".to_owned(),
+        CommandScope::UnitType(unit_type) => format!("{:?}", unit_type),
+    };
+
+    let mut text = text_query.single_mut();
+    text.sections[0].value = format!("Orders: {}", scope_name);
+}
@@ -0,0 +1,107 @@
+use bevy::prelude::*;
+
+use crate::{
+    combat::damage::{UnitDamaged, UnitHealed},
+    gamestate::Cleanup,
+};
+
+const DAMAGE_TEXT_LIFETIME: f32 = 0.8;
+const DAMAGE_TEXT_RISE_SPEED: f32 = 60.0;
+
+#[derive(Component)]
+pub struct DamageText {
+    pub timer: Timer,
+}
+
+fn spawn_floating_text(
+    commands: &mut Commands,
+    asset_server: &Res<AssetServer>,
+    position: Vec3,
+    value: String,
+    font_size: f32,
+    color: Color,
+) {
+    commands.spawn((
+        Text2dBundle {
+            text: Text::from_section(
+                value,
+                TextStyle {
+                    font: asset_server.load("fonts/JetBrainsMonoNerdFont-Regular.ttf"),
+                    font_size,
+                    color,
+                },
+            ),
+            transform: Transform::from_translation(
+                position.truncate().extend(20.0) + Vec3::Y * 32.0,
+            ),
+            ..default()
+        },
+        DamageText {
+            timer: Timer::from_seconds(DAMAGE_TEXT_LIFETIME, TimerMode::Once),
+        },
+        Cleanup,
+    ));
+}
+
+pub fn spawn_damage_text(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut damaged_events: EventReader<UnitDamaged>,
+    mut healed_events: EventReader<UnitHealed>,
+    target_query: Query<&GlobalTransform>,
+) {
+    for event in damaged_events.read() {
+        let Ok(target_transform) = target_query.get(event.target) else {
+            continue;
+        };
+
+        let (font_size, color) = if event.is_critical {
+            (40.0, Color::ORANGE)
+        } else {
+            (28.0, Color::WHITE)
+        };
+
+        spawn_floating_text(
+            &mut commands,
+            &asset_server,
+            target_transform.translation(),
+            event.amount.to_string(),
+            font_size,
+            color,
+        );
+    }
+
+    for event in healed_events.read() {
+        let Ok(target_transform) = target_query.get(event.target) else {
+            continue;
+        };
+
+        spawn_floating_text(
+            &mut commands,
+            &asset_server,
+            target_transform.translation(),
+            format!("+{}", event.amount),
+            28.0,
+            Color::GREEN,
+        );
+    }
+}
+
+pub fn update_damage_text(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut DamageText, &mut Transform, &mut Text)>,
+) {
+    for (entity, mut damage_text, mut transform, mut text) in query.iter_mut() {
+        if damage_text.timer.tick(time.delta()).finished() {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+
+        transform.translation.y += DAMAGE_TEXT_RISE_SPEED * time.delta_seconds();
+        let alpha = 1.0 - damage_text.timer.fraction();
+        for section in text.sections.iter_mut() {
+            section.style.color.set_a(alpha);
+        }
+    }
+}
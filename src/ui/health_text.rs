@@ -1,15 +1,20 @@
 use bevy::prelude::*;
 
-use crate::{player::plugin::Player, units::health::Health};
+use crate::{player::plugin::Player, structures::altar::Altar, units::health::Health};
 
 use super::plugin::HealthText;
 
 pub fn update_health_text(
     query: Query<&Health, With<Player>>,
+    altar_query: Query<&Health, With<Altar>>,
     mut text_query: Query<&mut Text, With<HealthText>>,
 ) {
     if let Some(health) = query.iter().next() {
         let mut text = text_query.single_mut();
-        text.sections[0].value = format!("HP: {}", health.0);
+        text.sections[0].value = format!("HP: {}/{}", health.current, health.max);
+        if let Some(altar_health) = altar_query.iter().next() {
+            text.sections[0].value +=
+                &format!("\nAltar: {}/{}", altar_health.current, altar_health.max);
+        }
     }
 }
@@ -0,0 +1,23 @@
+use bevy::prelude::*;
+
+use crate::gamestate::GameMode;
+
+use super::plugin::ModeText;
+
+fn get_toggle_text(key: &str, name: &str, is_on: bool) -> String {
+    format!("{} {}: {}", key, name, if is_on { "On" } else { "Off" })
+}
+
+pub fn update_mode_text(
+    game_mode: Res<GameMode>,
+    mut text_query: Query<&mut Text, With<ModeText>>,
+) {
+    let lines = [
+        get_toggle_text("F1", "Upkeep", game_mode.summon_upkeep),
+        get_toggle_text("F2", "Altar only loss", game_mode.altar_only_loss),
+        get_toggle_text("F3", "Friendly fire", game_mode.friendly_fire),
+    ];
+
+    let mut text = text_query.single_mut();
+    text.sections[0].value = lines.join("\n");
+}
@@ -1,8 +1,11 @@
-use bevy::prelude::*;
+use bevy::{prelude::*, sprite::Anchor, text::TextLayoutInfo};
 
 use crate::{dark_arts_defense::GameEvent, gamestate::GameState};
 
-use super::{health_text, mana_text, score_text};
+use super::{
+    boss_bar, command_text, damage_text, health_text, mana_text, mode_text, score_text, spell_text,
+    summon_text,
+};
 
 pub struct UiPlugin;
 
@@ -15,6 +18,27 @@ pub struct ManaText;
 #[derive(Component)]
 pub struct ScoreText;
 
+#[derive(Component)]
+pub struct CommandText;
+
+#[derive(Component)]
+pub struct SpellText;
+
+#[derive(Component)]
+pub struct SummonText;
+
+#[derive(Component)]
+pub struct ModeText;
+
+#[derive(Component)]
+pub struct BossBar;
+
+#[derive(Component)]
+pub struct BossBarFill;
+
+#[derive(Component)]
+pub struct BossText;
+
 #[derive(Component)]
 pub struct GameOverText;
 
@@ -26,9 +50,21 @@ impl Plugin for UiPlugin {
                 update_health_pos,
                 update_mana_pos,
                 update_score_pos,
+                update_command_pos,
+                update_spell_pos,
+                update_summon_pos,
+                update_boss_bar_pos.after(update_summon_pos),
+                update_mode_pos,
                 health_text::update_health_text,
                 mana_text::update_mana_text,
                 score_text::update_mana_text,
+                command_text::update_command_text,
+                spell_text::update_spell_text,
+                summon_text::update_summon_text,
+                boss_bar::update_boss_bar,
+                mode_text::update_mode_text,
+                damage_text::spawn_damage_text,
+                damage_text::update_damage_text,
                 game_over_ui,
             ),
         );
@@ -37,6 +73,8 @@ impl Plugin for UiPlugin {
 
 const TEXT_OFFSET_TOP: f32 = 0.15;
 const TEXT_OFFSET_CENTER: f32 = 0.3;
+const HUD_MARGIN: f32 = 16.0;
+const BOSS_BAR_SIZE: Vec2 = Vec2::new(600.0, 24.0);
 
 fn setup(mut commands: Commands, asset_server: Res<AssetServer>, window_query: Query<&Window>) {
     let font = asset_server.load("fonts/JetBrainsMonoNerdFont-Regular.ttf");
@@ -100,6 +138,138 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>, window_query: Q
         },
         ScoreText,
     ));
+    commands.spawn((
+        Text2dBundle {
+            text: Text::from_section(
+                "Orders: All",
+                TextStyle {
+                    font: font.clone(),
+                    font_size: 40.0,
+                    color: Color::PURPLE,
+                },
+            )
+            .with_justify(JustifyText::Left),
+            transform: Transform {
+                translation: Vec3::new(
+                    -window_bounds.x * TEXT_OFFSET_CENTER,
+                    -window_bounds.y,
+                    0.0,
+                ),
+                ..default()
+            },
+            ..default()
+        },
+        CommandText,
+    ));
+    commands.spawn((
+        Text2dBundle {
+            text: Text::from_section(
+                "",
+                TextStyle {
+                    font: font.clone(),
+                    font_size: 30.0,
+                    color: Color::PURPLE,
+                },
+            )
+            .with_justify(JustifyText::Left),
+            transform: Transform {
+                translation: Vec3::new(window_bounds.x * TEXT_OFFSET_CENTER, -window_bounds.y, 0.0),
+                ..default()
+            },
+            ..default()
+        },
+        SpellText,
+    ));
+    commands.spawn((
+        Text2dBundle {
+            text: Text::from_section(
+                "",
+                TextStyle {
+                    font: font.clone(),
+                    font_size: 30.0,
+                    color: Color::PURPLE,
+                },
+            )
+            .with_justify(JustifyText::Left),
+            // Grows downwards from the top edge, however many lines it has
+            text_anchor: Anchor::TopCenter,
+            transform: Transform {
+                translation: Vec3::new(0.0, window_bounds.y - HUD_MARGIN, 0.0),
+                ..default()
+            },
+            ..default()
+        },
+        SummonText,
+    ));
+    commands.spawn((
+        Text2dBundle {
+            text: Text::from_section(
+                "",
+                TextStyle {
+                    font: font.clone(),
+                    font_size: 24.0,
+                    color: Color::GRAY,
+                },
+            )
+            .with_justify(JustifyText::Left),
+            text_anchor: Anchor::TopLeft,
+            transform: Transform {
+                translation: Vec3::new(
+                    -window_bounds.x + HUD_MARGIN,
+                    window_bounds.y - HUD_MARGIN,
+                    0.0,
+                ),
+                ..default()
+            },
+            ..default()
+        },
+        ModeText,
+    ));
+    commands
+        .spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: Color::rgba(0.1, 0.1, 0.1, 0.8),
+                    custom_size: Some(BOSS_BAR_SIZE),
+                    ..default()
+                },
+                visibility: Visibility::Hidden,
+                ..default()
+            },
+            BossBar,
+        ))
+        .with_children(|parent| {
+            // Anchored on the left edge so scaling it shrinks the bar towards the left
+            parent.spawn((
+                SpriteBundle {
+                    sprite: Sprite {
+                        color: Color::CRIMSON,
+                        custom_size: Some(BOSS_BAR_SIZE),
+                        anchor: Anchor::CenterLeft,
+                        ..default()
+                    },
+                    transform: Transform::from_xyz(-BOSS_BAR_SIZE.x * 0.5, 0.0, 0.1),
+                    ..default()
+                },
+                BossBarFill,
+            ));
+            parent.spawn((
+                Text2dBundle {
+                    text: Text::from_section(
+                        "",
+                        TextStyle {
+                            font: font.clone(),
+                            font_size: 30.0,
+                            color: Color::WHITE,
+                        },
+                    )
+                    .with_justify(JustifyText::Center),
+                    transform: Transform::from_xyz(0.0, BOSS_BAR_SIZE.y * 1.5, 0.2),
+                    ..default()
+                },
+                BossText,
+            ));
+        });
     commands.spawn((
         Text2dBundle {
             text: Text::from_section(
@@ -155,6 +325,21 @@ fn update_score_pos(
     );
 }
 
+fn update_command_pos(
+    window_query: Query<&Window>,
+    mut query: Query<&mut Transform, With<CommandText>>,
+) {
+    let window = window_query.single();
+    let window_bounds = Vec2::new(window.width(), window.height()) * 0.5;
+
+    let mut transform = query.single_mut();
+    transform.translation = Vec3::new(
+        -window_bounds.x * TEXT_OFFSET_CENTER,
+        -window_bounds.y + window_bounds.y * TEXT_OFFSET_TOP,
+        0.0,
+    );
+}
+
 fn game_over_ui(
     keys: Res<ButtonInput<KeyCode>>,
     mut visible_query: Query<&mut Visibility, With<GameOverText>>,
@@ -175,3 +360,55 @@ fn game_over_ui(
         }
     }
 }
+
+fn update_spell_pos(
+    window_query: Query<&Window>,
+    mut query: Query<&mut Transform, With<SpellText>>,
+) {
+    let window = window_query.single();
+    let window_bounds = Vec2::new(window.width(), window.height()) * 0.5;
+
+    let mut transform = query.single_mut();
+    transform.translation = Vec3::new(
+        window_bounds.x * TEXT_OFFSET_CENTER,
+        -window_bounds.y + window_bounds.y * TEXT_OFFSET_TOP,
+        0.0,
+    );
+}
+
+fn update_summon_pos(
+    window_query: Query<&Window>,
+    mut query: Query<&mut Transform, With<SummonText>>,
+) {
+    let window = window_query.single();
+    let window_bounds = Vec2::new(window.width(), window.height()) * 0.5;
+
+    let mut transform = query.single_mut();
+    transform.translation = Vec3::new(0.0, window_bounds.y - HUD_MARGIN, 0.0);
+}
+
+fn update_mode_pos(window_query: Query<&Window>, mut query: Query<&mut Transform, With<ModeText>>) {
+    let window = window_query.single();
+    let window_bounds = Vec2::new(window.width(), window.height()) * 0.5;
+
+    let mut transform = query.single_mut();
+    transform.translation = Vec3::new(
+        -window_bounds.x + HUD_MARGIN,
+        window_bounds.y - HUD_MARGIN,
+        0.0,
+    );
+}
+
+// Sits right below the summon text, which changes height with its cooldowns and upkeep lines
+#[allow(clippy::type_complexity)]
+fn update_boss_bar_pos(
+    summon_query: Query<(&Transform, &TextLayoutInfo), (With<SummonText>, Without<BossBar>)>,
+    mut query: Query<&mut Transform, With<BossBar>>,
+) {
+    let (summon_transform, summon_layout) = summon_query.single();
+    let summon_bottom = summon_transform.translation.y - summon_layout.logical_size.y;
+
+    // Leaves room for the label above the bar
+    let mut transform = query.single_mut();
+    transform.translation = Vec3::new(0.0, summon_bottom - HUD_MARGIN - BOSS_BAR_SIZE.y * 3.0, 0.0);
+}
@@ -0,0 +1,31 @@
+use bevy::prelude::*;
+
+use crate::player::spellbook::Spellbook;
+
+use super::plugin::SpellText;
+
+pub fn update_spell_text(
+    spellbook_query: Query<&Spellbook>,
+    mut text_query: Query<&mut Text, With<SpellText>>,
+) {
+    let mut text = text_query.single_mut();
+    let Ok(spellbook) = spellbook_query.get_single() else {
+        text.sections[0].value = String::new();
+        return;
+    };
+
+    text.sections[0].value = spellbook
+        .0
+        .iter()
+        .map(|spell| {
+            let key = format!("{:?}", spell.key).replace("Key", "");
+            let cooldown = spell.cooldown_timer.remaining_secs();
+            if cooldown > 0.0 {
+                format!("{} {:?} ({:.0}s)", key, spell.kind, cooldown.ceil())
+            } else {
+                format!("{} {:?} [{}]", key, spell.kind, spell.mana_cost)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+}
@@ -0,0 +1,55 @@
+use bevy::prelude::*;
+
+use crate::gamestate::GameMode;
+use crate::player::summoning::{
+    get_placement_binds, get_upkeep, get_used_supply, Placeable, SummonCooldowns, POPULATION_CAP,
+    UPKEEP_INTERVAL,
+};
+use crate::units::health::Health;
+use crate::units::team::CurrentTeam;
+use crate::units::unit_types::{UnitResource, UnitType};
+
+use super::plugin::SummonText;
+
+pub fn update_summon_text(
+    game_mode: Res<GameMode>,
+    unit_configs: Res<UnitResource>,
+    cooldowns: Res<SummonCooldowns>,
+    units_query: Query<(&UnitType, &CurrentTeam, &Health)>,
+    mut text_query: Query<&mut Text, With<SummonText>>,
+) {
+    let mut lines = vec![format!(
+        "Supply: {}/{}",
+        get_used_supply(&unit_configs, units_query.iter()),
+        POPULATION_CAP
+    )];
+
+    if game_mode.summon_upkeep {
+        lines.push(format!(
+            "Upkeep: {}/{}s",
+            get_upkeep(&unit_configs, units_query.iter()),
+            UPKEEP_INTERVAL
+        ));
+    }
+
+    for (key, placeable) in get_placement_binds() {
+        let Placeable::Unit(unit) = placeable else {
+            continue;
+        };
+
+        let Some(config) = unit_configs.try_get(unit) else {
+            continue;
+        };
+
+        let key = format!("{:?}", key).replace("Digit", "");
+        let cooldown = cooldowns.remaining_secs(unit);
+        if cooldown > 0.0 {
+            lines.push(format!("{} {:?} ({:.0}s)", key, unit, cooldown.ceil()));
+        } else {
+            lines.push(format!("{} {:?} [{}]", key, unit, config.cost));
+        }
+    }
+
+    let mut text = text_query.single_mut();
+    text.sections[0].value = lines.join("\n");
+}
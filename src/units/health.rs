@@ -1,16 +1,31 @@
 use bevy::prelude::*;
 
-#[derive(Component)]
-pub struct Health(pub u8);
+#[derive(Component, Clone, Copy, Debug)]
+pub struct Health {
+    pub current: u32,
+    pub max: u32,
+}
 
 impl Default for Health {
     fn default() -> Self {
-        Health(100)
+        Health::new(100)
     }
 }
 
 impl Health {
+    pub fn new(max: u32) -> Self {
+        Health { current: max, max }
+    }
+
     pub fn is_dead(&self) -> bool {
-        self.0 == 0
+        self.current == 0
+    }
+
+    pub fn fraction(&self) -> f32 {
+        if self.max == 0 {
+            return 0.0;
+        }
+
+        self.current as f32 / self.max as f32
     }
 }
@@ -0,0 +1,163 @@
+use bevy::prelude::*;
+
+use crate::{ai::behavior::AttackBehavior, movement::Movement, units::health::Health};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Stat {
+    MaxHealth,
+    Speed,
+    Damage,
+    AttackCooldown,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ModifierKind {
+    Additive(f32),       // Added to the base value
+    Multiplicative(f32), // Multiplies the base value plus all additive modifiers
+}
+
+#[derive(Debug, Clone)]
+pub struct StatModifier {
+    pub stat: Stat,
+    pub kind: ModifierKind,
+    pub source: &'static str,
+    pub timer: Option<Timer>, // Permanent when there is no timer
+}
+
+impl StatModifier {
+    pub fn new(stat: Stat, kind: ModifierKind, source: &'static str) -> Self {
+        StatModifier {
+            stat,
+            kind,
+            source,
+            timer: None,
+        }
+    }
+
+    pub fn with_duration(mut self, duration: f32) -> Self {
+        self.timer = Some(Timer::from_seconds(duration, TimerMode::Once));
+        self
+    }
+}
+
+// The values the unit was spawned with, modifiers are always applied on top of these
+#[derive(Component, Clone, Copy, Debug)]
+pub struct BaseStats {
+    pub max_health: u32,
+    pub speed: f32,
+    pub damage: u32,
+    pub attack_cooldown: f32,
+}
+
+#[derive(Component, Default, Clone, Debug)]
+pub struct StatModifiers(pub Vec<StatModifier>);
+
+impl StatModifiers {
+    // A source only gets one modifier per stat, adding it again refreshes it instead of stacking
+    pub fn add(&mut self, modifier: StatModifier) {
+        self.0.retain(|existing| {
+            existing.source != modifier.source || existing.stat != modifier.stat
+        });
+        self.0.push(modifier);
+    }
+
+    pub fn remove_source(&mut self, source: &'static str) {
+        self.0.retain(|existing| existing.source != source);
+    }
+
+    pub fn has_source(&self, source: &'static str) -> bool {
+        self.0.iter().any(|existing| existing.source == source)
+    }
+
+    pub fn apply(&self, stat: Stat, base: f32) -> f32 {
+        let (additive, multiplicative) =
+            self.0.iter().filter(|modifier| modifier.stat == stat).fold(
+                (0.0, 1.0),
+                |(additive, multiplicative), modifier| match modifier.kind {
+                    ModifierKind::Additive(value) => (additive + value, multiplicative),
+                    ModifierKind::Multiplicative(value) => (additive, multiplicative * value),
+                },
+            );
+
+        ((base + additive) * multiplicative).max(0.0)
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn init_base_stats(
+    mut commands: Commands,
+    query: Query<
+        (Entity, &Health, &Movement, Option<&AttackBehavior>),
+        (With<StatModifiers>, Without<BaseStats>),
+    >,
+) {
+    for (entity, health, movement, attack_behavior) in query.iter() {
+        commands.entity(entity).insert(BaseStats {
+            max_health: health.max,
+            speed: movement.speed,
+            damage: attack_behavior.map_or(0, |attack_behavior| attack_behavior.damage),
+            attack_cooldown: attack_behavior
+                .map_or(0.0, |attack_behavior| attack_behavior.cooldown),
+        });
+    }
+}
+
+pub fn tick_stat_modifiers(time: Res<Time>, mut query: Query<&mut StatModifiers>) {
+    for mut stat_modifiers in query.iter_mut() {
+        // Every timer has to be ticked, stopping at the first expired one would stretch the rest
+        let mut has_expired = false;
+        for modifier in stat_modifiers.bypass_change_detection().0.iter_mut() {
+            if let Some(timer) = modifier.timer.as_mut() {
+                has_expired |= timer.tick(time.delta()).finished();
+            }
+        }
+
+        if has_expired {
+            stat_modifiers.0.retain(|modifier| {
+                modifier
+                    .timer
+                    .as_ref()
+                    .is_none_or(|timer| !timer.finished())
+            });
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn apply_stat_modifiers(
+    mut query: Query<
+        (
+            &StatModifiers,
+            &BaseStats,
+            &mut Health,
+            &mut Movement,
+            Option<&mut AttackBehavior>,
+        ),
+        Or<(Changed<StatModifiers>, Added<BaseStats>)>,
+    >,
+) {
+    for (stat_modifiers, base_stats, mut health, mut movement, attack_behavior) in query.iter_mut()
+    {
+        movement.speed = stat_modifiers.apply(Stat::Speed, base_stats.speed);
+
+        // Keep the missing health the same when the max changes, but never kill the unit with it
+        let max_health = stat_modifiers
+            .apply(Stat::MaxHealth, base_stats.max_health as f32)
+            .round()
+            .max(1.0) as u32;
+        if max_health != health.max && !health.is_dead() {
+            let missing_health = health.max.saturating_sub(health.current);
+            health.max = max_health;
+            health.current = max_health.saturating_sub(missing_health).max(1);
+        }
+
+        if let Some(mut attack_behavior) = attack_behavior {
+            attack_behavior.damage = stat_modifiers
+                .apply(Stat::Damage, base_stats.damage as f32)
+                .round() as u32;
+            attack_behavior.cooldown = stat_modifiers
+                .apply(Stat::AttackCooldown, base_stats.attack_cooldown)
+                .max(0.1);
+        }
+    }
+}
@@ -1,13 +1,13 @@
 use bevy::prelude::*;
 
-#[derive(Eq, PartialEq, Default, Clone)]
+#[derive(Eq, PartialEq, Default, Clone, Debug)]
 pub enum Team {
     #[default]
     Evil, // In this game, the player is evil
     Good,
 }
 
-#[derive(Component, Default, Clone)]
+#[derive(Component, Default, Clone, Debug)]
 pub struct CurrentTeam(pub Team);
 
 impl CurrentTeam {
@@ -1,12 +1,20 @@
+use crate::ai::ability::{Abilities, Ability, AbilityEffect, AbilityTargeting};
 use crate::ai::behavior::{
-    AttackBehavior, Behavior, BehaviorBundle, ChaseBehavior, CurrentBehavior, DeadBehavior,
-    FleeBehavior, IdleBehavior, MoveOrigoBehavior, SupportedBehaviors, WanderBehavior,
+    AttackBehavior, Behavior, BehaviorBundle, ChaseBehavior, CommandBehavior, CurrentBehavior,
+    DeadBehavior, FleeBehavior, FollowBehavior, GuardBehavior, HealBehavior, IdleBehavior,
+    MoveOrigoBehavior, RangedAttackBehavior, SupportedBehaviors, WanderBehavior,
 };
-use crate::animation::{spawn_animated_children, CurrentAnimation};
+use crate::ai::targeting::TargetSelection;
+use crate::animation::{spawn_animated_children, BaseTint, CurrentAnimation};
 use crate::animation::{AnimatedChildSpawnParams, AnimationType};
+use crate::collision::Collider;
+use crate::combat::area::{AreaAttack, AreaOrigin, AreaShape, FriendlyFire};
+use crate::combat::damage::{Armor, CriticalStrike, DamageKind, Resistances};
+use crate::combat::projectile::ProjectileParams;
+use crate::combat::status::{OnHitStatusEffects, StatusEffect, StatusEffects};
 use crate::gamestate::Cleanup;
 use crate::movement::Movement;
-use crate::units::{health::Health, team::CurrentTeam};
+use crate::units::{health::Health, stats::StatModifiers, team::CurrentTeam, veterancy::Veterancy};
 use crate::velocity::Velocity;
 use bevy::ecs::system::EntityCommands;
 use bevy::prelude::*;
@@ -14,13 +22,21 @@ use std::collections::HashMap;
 
 use super::team::Team;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum UnitType {
     Acolyte,
     Warrior,
     Cat,
+    Warlock,
+    Mender,
 
     Knight,
+    Archer,
+    Priest,
+    Scout,
+    Paladin,
+    Siege,
+    Warlord,
 }
 
 #[derive(Bundle, Default)]
@@ -28,16 +44,25 @@ pub struct UnitBundle {
     pub movement: Movement,
     pub velocity: Velocity,
     pub current_animation: CurrentAnimation,
+    pub base_tint: BaseTint,
     pub transform: Transform,
     pub global_transform: GlobalTransform,
     pub inherited_visibility: InheritedVisibility,
     pub health: Health,
     pub team: CurrentTeam,
+    pub collider: Collider,
+    pub armor: Armor,
+    pub resistances: Resistances,
+    pub critical_strike: CriticalStrike,
+    pub stat_modifiers: StatModifiers,
+    pub status_effects: StatusEffects,
+    pub on_hit_status_effects: OnHitStatusEffects,
     pub cleanup: Cleanup,
 }
 
 // Create a trait that will be used to define the components of the units
 pub trait UnitChildrenSpawnParamsFactory {
+    fn unit_type(&self) -> UnitType;
     fn create_unit_bundle(&self) -> UnitBundle;
     fn create_behavior_bundle(&self) -> BehaviorBundle;
     fn create_children_spawn_params(&self) -> Vec<AnimatedChildSpawnParams>;
@@ -46,7 +71,7 @@ pub trait UnitChildrenSpawnParamsFactory {
 #[derive(Component, Clone)]
 pub struct Acolyte {
     pub give_mana_timer: Timer,
-    pub mana_amount: u8,
+    pub mana_amount: u32,
 }
 
 impl Default for Acolyte {
@@ -60,10 +85,15 @@ impl Default for Acolyte {
 }
 
 impl UnitChildrenSpawnParamsFactory for Acolyte {
+    fn unit_type(&self) -> UnitType {
+        UnitType::Acolyte
+    }
+
     fn create_unit_bundle(&self) -> UnitBundle {
         UnitBundle {
             movement: Movement { speed: 75.0 },
-            health: Health(50),
+            collider: Collider { radius: 16.0 },
+            health: Health::new(50),
             transform: Transform::from_scale(Vec3::splat(0.8)),
             ..default()
         }
@@ -74,9 +104,21 @@ impl UnitChildrenSpawnParamsFactory for Acolyte {
             current_behavior: CurrentBehavior(Behavior::Idle(IdleBehavior {})),
             supported_behaviors: SupportedBehaviors(vec![
                 (Behavior::Idle(IdleBehavior {}), 5),
-                (Behavior::Flee(FleeBehavior {}), 10),
+                (Behavior::Command(CommandBehavior {}), 8),
+                (Behavior::Flee(FleeBehavior::default()), 10),
                 (Behavior::Dead(DeadBehavior {}), 15),
             ]),
+            abilities: Abilities(vec![Ability::new(
+                "Heal",
+                5.0,
+                1.0,
+                250.0,
+                AbilityTargeting::WoundedAlly {
+                    health_fraction: 0.6,
+                },
+                vec![AbilityEffect::Heal { amount: 25 }],
+            )]),
+            ..default()
         }
     }
 
@@ -119,17 +161,71 @@ impl UnitChildrenSpawnParamsFactory for Acolyte {
 #[derive(Component, Clone)]
 pub struct Warrior;
 impl UnitChildrenSpawnParamsFactory for Warrior {
+    fn unit_type(&self) -> UnitType {
+        UnitType::Warrior
+    }
+
     fn create_unit_bundle(&self) -> UnitBundle {
         UnitBundle {
             movement: Movement { speed: 200.0 },
-            health: Health(255),
+            collider: Collider { radius: 26.0 },
+            armor: Armor(3),
+            on_hit_status_effects: OnHitStatusEffects(vec![StatusEffect::slow(0.3, 2.0)]),
+            health: Health::new(300),
             transform: Transform::from_scale(Vec3::splat(1.8)),
             ..default()
         }
     }
 
     fn create_behavior_bundle(&self) -> BehaviorBundle {
-        BehaviorBundle::default()
+        // The warrior goes for whoever would hurt the rest of the summons the most
+        BehaviorBundle {
+            supported_behaviors: SupportedBehaviors(vec![
+                (Behavior::Follow(FollowBehavior::default()), 5),
+                (Behavior::Guard(GuardBehavior::default()), 6),
+                (Behavior::Command(CommandBehavior {}), 8),
+                (Behavior::Chase(ChaseBehavior::default()), 10),
+                (
+                    Behavior::Attack(AttackBehavior {
+                        range: 110.0,
+                        engage_distance: 84.0,
+                        ..default()
+                    }),
+                    15,
+                ),
+                (Behavior::Dead(DeadBehavior {}), 20),
+            ]),
+            target_selection: TargetSelection::HighestThreat,
+            abilities: Abilities(vec![
+                Ability::new(
+                    "Taunt",
+                    10.0,
+                    0.0,
+                    220.0,
+                    AbilityTargeting::NearbyEnemies { count: 1 },
+                    vec![AbilityEffect::Taunt {
+                        radius: 220.0,
+                        duration: 4.0,
+                        threat: 100.0,
+                    }],
+                ),
+                Ability::new(
+                    "Shield Bash",
+                    6.0,
+                    0.3,
+                    110.0,
+                    AbilityTargeting::Enemy,
+                    vec![
+                        AbilityEffect::Damage {
+                            amount: 10,
+                            kind: DamageKind::Physical,
+                        },
+                        AbilityEffect::Status(StatusEffect::stun(1.5)),
+                    ],
+                ),
+            ]),
+            ..default()
+        }
     }
 
     fn create_children_spawn_params(&self) -> Vec<AnimatedChildSpawnParams> {
@@ -179,18 +275,63 @@ impl UnitChildrenSpawnParamsFactory for Warrior {
 
 #[derive(Component, Clone)]
 pub struct Cat;
+
+impl Cat {
+    // The long attack animation sweeps the claws across everything in front of the cat
+    pub fn sweep_attack() -> AreaAttack {
+        AreaAttack {
+            shape: AreaShape::Cone {
+                radius: 110.0,
+                angle: 100f32.to_radians(),
+            },
+            origin: AreaOrigin::Attacker,
+            falloff: 0.3,
+            friendly_fire: FriendlyFire::FromGameMode,
+        }
+    }
+}
 impl UnitChildrenSpawnParamsFactory for Cat {
+    fn unit_type(&self) -> UnitType {
+        UnitType::Cat
+    }
+
     fn create_unit_bundle(&self) -> UnitBundle {
         UnitBundle {
             movement: Movement { speed: 300.0 },
-            health: Health(125),
+            collider: Collider { radius: 22.0 },
+            critical_strike: CriticalStrike {
+                chance: 0.25,
+                ..default()
+            },
+            on_hit_status_effects: OnHitStatusEffects(vec![StatusEffect::poison(2.0, 4.0)]),
+            health: Health::new(125),
             transform: Transform::from_scale(Vec3::splat(1.4)),
             ..default()
         }
     }
 
     fn create_behavior_bundle(&self) -> BehaviorBundle {
-        BehaviorBundle::default()
+        // The cat finishes off wounded enemies
+        BehaviorBundle {
+            target_selection: TargetSelection::LowestHealth,
+            abilities: Abilities(vec![Ability::new(
+                "Pounce",
+                8.0,
+                0.2,
+                280.0,
+                AbilityTargeting::Enemy,
+                vec![
+                    AbilityEffect::Dash { speed: 900.0 },
+                    AbilityEffect::Damage {
+                        amount: 15,
+                        kind: DamageKind::Physical,
+                    },
+                    AbilityEffect::Status(StatusEffect::stun(1.0)),
+                ],
+            )
+            .with_min_range(120.0)]),
+            ..default()
+        }
     }
 
     fn create_children_spawn_params(&self) -> Vec<AnimatedChildSpawnParams> {
@@ -238,13 +379,119 @@ impl UnitChildrenSpawnParamsFactory for Cat {
     }
 }
 
+#[derive(Component, Clone)]
+pub struct Warlock;
+impl UnitChildrenSpawnParamsFactory for Warlock {
+    fn unit_type(&self) -> UnitType {
+        UnitType::Warlock
+    }
+
+    fn create_unit_bundle(&self) -> UnitBundle {
+        UnitBundle {
+            movement: Movement { speed: 120.0 },
+            collider: Collider { radius: 16.0 },
+            base_tint: BaseTint(Color::rgb(0.7, 0.5, 1.0)),
+            health: Health::new(70),
+            transform: Transform::from_scale(Vec3::splat(0.9)),
+            ..default()
+        }
+    }
+
+    fn create_behavior_bundle(&self) -> BehaviorBundle {
+        let attack_cooldown = 2.0;
+        let dark_bolt = RangedAttackBehavior {
+            attack: AttackBehavior {
+                range: 300.0,
+                engage_distance: 220.0,
+                cooldown: attack_cooldown,
+                damage: 12,
+                timer: Timer::from_seconds(attack_cooldown, TimerMode::Once),
+                ..default()
+            },
+            projectile: ProjectileParams {
+                speed: 350.0,
+                lifetime: 3.0,
+                kind: DamageKind::Dark,
+                is_homing: true,
+                turn_rate: 5.0,
+                size: Vec2::splat(12.0),
+                color: Color::rgb(0.6, 0.2, 0.9),
+                ..default()
+            },
+        };
+
+        BehaviorBundle {
+            supported_behaviors: SupportedBehaviors(vec![
+                (Behavior::Follow(FollowBehavior::default()), 5),
+                (Behavior::Guard(GuardBehavior::default()), 6),
+                (Behavior::Command(CommandBehavior {}), 8),
+                (Behavior::Chase(ChaseBehavior::default()), 10),
+                (Behavior::RangedAttack(dark_bolt), 15),
+                (Behavior::Dead(DeadBehavior {}), 20),
+            ]),
+            ..default()
+        }
+    }
+
+    fn create_children_spawn_params(&self) -> Vec<AnimatedChildSpawnParams> {
+        [
+            (
+                "acolyte/acolyte_idle.png",
+                Vec2::new(80.0, 80.0),
+                (3, 4),
+                9,
+                AnimationType::Idle,
+                true,
+                false,
+            ),
+            (
+                "acolyte/acolyte_idle.png",
+                Vec2::new(80.0, 80.0),
+                (3, 4),
+                9,
+                AnimationType::Walk,
+                true,
+                false,
+            ),
+            (
+                "acolyte/acolyte_death.png",
+                Vec2::new(80.0, 80.0),
+                (3, 4),
+                9,
+                AnimationType::Death,
+                false,
+                false,
+            ),
+            (
+                "acolyte/acolyte_summon.png",
+                Vec2::new(80.0, 80.0),
+                (2, 3),
+                5,
+                AnimationType::Attack,
+                false,
+                true,
+            ),
+        ]
+        .into_iter()
+        .map(|data| data.into())
+        .collect()
+    }
+}
+
 #[derive(Component, Clone)]
 pub struct Knight;
 impl UnitChildrenSpawnParamsFactory for Knight {
+    fn unit_type(&self) -> UnitType {
+        UnitType::Knight
+    }
+
     fn create_unit_bundle(&self) -> UnitBundle {
         UnitBundle {
             movement: Movement { speed: 250.0 },
-            health: Health(90),
+            collider: Collider { radius: 24.0 },
+            armor: Armor(2),
+            resistances: Resistances([(DamageKind::Holy, 0.5)].into_iter().collect()),
+            health: Health::new(90),
             transform: Transform::from_scale(Vec3::splat(1.5)),
             ..default()
         }
@@ -255,11 +502,36 @@ impl UnitChildrenSpawnParamsFactory for Knight {
             supported_behaviors: SupportedBehaviors(vec![
                 (Behavior::Wander(WanderBehavior::default()), 3),
                 (Behavior::MoveOrigo(MoveOrigoBehavior {}), 5),
-                (Behavior::Chase(ChaseBehavior {}), 10),
-                (Behavior::Attack(AttackBehavior::default()), 15),
+                (Behavior::Chase(ChaseBehavior::default()), 10),
+                (
+                    Behavior::Attack(AttackBehavior {
+                        range: 120.0,
+                        engage_distance: 90.0,
+                        ..default()
+                    }),
+                    15,
+                ),
                 (Behavior::Dead(DeadBehavior {}), 20),
             ]),
             current_behavior: CurrentBehavior(Behavior::MoveOrigo(MoveOrigoBehavior {})),
+            target_selection: TargetSelection::HighestThreat,
+            abilities: Abilities(vec![Ability::new(
+                "Charge",
+                12.0,
+                0.5,
+                400.0,
+                AbilityTargeting::Enemy,
+                vec![
+                    AbilityEffect::Dash { speed: 700.0 },
+                    AbilityEffect::Damage {
+                        amount: 20,
+                        kind: DamageKind::Physical,
+                    },
+                    AbilityEffect::Status(StatusEffect::slow(0.5, 2.0)),
+                ],
+            )
+            .with_min_range(160.0)]),
+            ..default()
         }
     }
 
@@ -307,6 +579,370 @@ impl UnitChildrenSpawnParamsFactory for Knight {
         .collect()
     }
 }
+
+#[derive(Component, Clone)]
+pub struct Archer;
+impl UnitChildrenSpawnParamsFactory for Archer {
+    fn unit_type(&self) -> UnitType {
+        UnitType::Archer
+    }
+
+    fn create_unit_bundle(&self) -> UnitBundle {
+        UnitBundle {
+            movement: Movement { speed: 220.0 },
+            collider: Collider { radius: 20.0 },
+            base_tint: BaseTint(Color::rgb(0.6, 0.9, 0.6)),
+            health: Health::new(60),
+            transform: Transform::from_scale(Vec3::splat(1.3)),
+            ..default()
+        }
+    }
+
+    fn create_behavior_bundle(&self) -> BehaviorBundle {
+        let attack_cooldown = 2.5;
+        let arrow = RangedAttackBehavior {
+            attack: AttackBehavior {
+                range: 360.0,
+                engage_distance: 280.0,
+                cooldown: attack_cooldown,
+                damage: 8,
+                timer: Timer::from_seconds(attack_cooldown, TimerMode::Once),
+                ..default()
+            },
+            projectile: ProjectileParams {
+                speed: 600.0,
+                size: Vec2::new(20.0, 3.0),
+                color: Color::rgb(0.8, 0.6, 0.3),
+                ..default()
+            },
+        };
+
+        BehaviorBundle {
+            supported_behaviors: SupportedBehaviors(vec![
+                (Behavior::Wander(WanderBehavior::default()), 3),
+                (Behavior::MoveOrigo(MoveOrigoBehavior {}), 5),
+                (Behavior::Chase(ChaseBehavior::default()), 10),
+                (Behavior::RangedAttack(arrow), 15),
+                (Behavior::Dead(DeadBehavior {}), 20),
+            ]),
+            current_behavior: CurrentBehavior(Behavior::MoveOrigo(MoveOrigoBehavior {})),
+            ..default()
+        }
+    }
+
+    // The archer is a knight without the armor, for now
+    fn create_children_spawn_params(&self) -> Vec<AnimatedChildSpawnParams> {
+        Knight.create_children_spawn_params()
+    }
+}
+
+// A summon that keeps the rest of the army alive, it heals instead of fighting
+#[derive(Component, Clone)]
+pub struct Mender;
+impl UnitChildrenSpawnParamsFactory for Mender {
+    fn unit_type(&self) -> UnitType {
+        UnitType::Mender
+    }
+
+    fn create_unit_bundle(&self) -> UnitBundle {
+        UnitBundle {
+            movement: Movement { speed: 110.0 },
+            collider: Collider { radius: 16.0 },
+            base_tint: BaseTint(Color::rgb(0.5, 1.0, 0.6)),
+            health: Health::new(60),
+            transform: Transform::from_scale(Vec3::splat(0.8)),
+            ..default()
+        }
+    }
+
+    fn create_behavior_bundle(&self) -> BehaviorBundle {
+        BehaviorBundle {
+            supported_behaviors: SupportedBehaviors(vec![
+                (Behavior::Follow(FollowBehavior::default()), 5),
+                (Behavior::Guard(GuardBehavior::default()), 6),
+                (Behavior::Command(CommandBehavior {}), 8),
+                (Behavior::Heal(HealBehavior::default()), 10),
+                (Behavior::Flee(FleeBehavior { radius: 160.0 }), 12),
+                (Behavior::Dead(DeadBehavior {}), 20),
+            ]),
+            ..default()
+        }
+    }
+
+    fn create_children_spawn_params(&self) -> Vec<AnimatedChildSpawnParams> {
+        Acolyte::default().create_children_spawn_params()
+    }
+}
+
+// The enemies' healer, it follows the knights around and patches them up
+#[derive(Component, Clone)]
+pub struct Priest;
+impl UnitChildrenSpawnParamsFactory for Priest {
+    fn unit_type(&self) -> UnitType {
+        UnitType::Priest
+    }
+
+    fn create_unit_bundle(&self) -> UnitBundle {
+        UnitBundle {
+            movement: Movement { speed: 180.0 },
+            collider: Collider { radius: 20.0 },
+            base_tint: BaseTint(Color::rgb(1.0, 0.95, 0.6)),
+            resistances: Resistances([(DamageKind::Holy, 0.5)].into_iter().collect()),
+            health: Health::new(50),
+            transform: Transform::from_scale(Vec3::splat(1.3)),
+            ..default()
+        }
+    }
+
+    fn create_behavior_bundle(&self) -> BehaviorBundle {
+        BehaviorBundle {
+            supported_behaviors: SupportedBehaviors(vec![
+                (Behavior::Wander(WanderBehavior::default()), 3),
+                (Behavior::MoveOrigo(MoveOrigoBehavior {}), 5),
+                (
+                    Behavior::Heal(HealBehavior {
+                        amount: 5,
+                        ..default()
+                    }),
+                    10,
+                ),
+                (Behavior::Flee(FleeBehavior { radius: 200.0 }), 12),
+                (Behavior::Dead(DeadBehavior {}), 20),
+            ]),
+            current_behavior: CurrentBehavior(Behavior::MoveOrigo(MoveOrigoBehavior {})),
+            ..default()
+        }
+    }
+
+    fn create_children_spawn_params(&self) -> Vec<AnimatedChildSpawnParams> {
+        Knight.create_children_spawn_params()
+    }
+}
+
+// Fast and fragile, picks off whatever is already hurt
+#[derive(Component, Clone)]
+pub struct Scout;
+impl UnitChildrenSpawnParamsFactory for Scout {
+    fn unit_type(&self) -> UnitType {
+        UnitType::Scout
+    }
+
+    fn create_unit_bundle(&self) -> UnitBundle {
+        UnitBundle {
+            movement: Movement { speed: 320.0 },
+            collider: Collider { radius: 18.0 },
+            base_tint: BaseTint(Color::rgb(0.5, 0.95, 0.95)),
+            health: Health::new(45),
+            transform: Transform::from_scale(Vec3::splat(1.2)),
+            ..default()
+        }
+    }
+
+    fn create_behavior_bundle(&self) -> BehaviorBundle {
+        let attack_cooldown = 2.0;
+        BehaviorBundle {
+            supported_behaviors: SupportedBehaviors(vec![
+                (Behavior::Wander(WanderBehavior::default()), 3),
+                (Behavior::MoveOrigo(MoveOrigoBehavior {}), 5),
+                (Behavior::Chase(ChaseBehavior::default()), 10),
+                (
+                    Behavior::Attack(AttackBehavior {
+                        cooldown: attack_cooldown,
+                        damage: 6,
+                        timer: Timer::from_seconds(attack_cooldown, TimerMode::Once),
+                        ..default()
+                    }),
+                    15,
+                ),
+                (Behavior::Dead(DeadBehavior {}), 20),
+            ]),
+            current_behavior: CurrentBehavior(Behavior::MoveOrigo(MoveOrigoBehavior {})),
+            target_selection: TargetSelection::LowestHealth,
+            ..default()
+        }
+    }
+
+    fn create_children_spawn_params(&self) -> Vec<AnimatedChildSpawnParams> {
+        Knight.create_children_spawn_params()
+    }
+}
+
+// Slow and heavily armored, shrugs off holy damage
+#[derive(Component, Clone)]
+pub struct Paladin;
+impl UnitChildrenSpawnParamsFactory for Paladin {
+    fn unit_type(&self) -> UnitType {
+        UnitType::Paladin
+    }
+
+    fn create_unit_bundle(&self) -> UnitBundle {
+        UnitBundle {
+            movement: Movement { speed: 160.0 },
+            collider: Collider { radius: 28.0 },
+            base_tint: BaseTint(Color::rgb(1.0, 0.7, 0.35)),
+            armor: Armor(5),
+            resistances: Resistances([(DamageKind::Holy, 0.75)].into_iter().collect()),
+            health: Health::new(220),
+            transform: Transform::from_scale(Vec3::splat(1.8)),
+            ..default()
+        }
+    }
+
+    fn create_behavior_bundle(&self) -> BehaviorBundle {
+        BehaviorBundle {
+            supported_behaviors: SupportedBehaviors(vec![
+                (Behavior::Wander(WanderBehavior::default()), 3),
+                (Behavior::MoveOrigo(MoveOrigoBehavior {}), 5),
+                (Behavior::Chase(ChaseBehavior::default()), 10),
+                (
+                    Behavior::Attack(AttackBehavior {
+                        range: 130.0,
+                        engage_distance: 96.0,
+                        damage: 18,
+                        ..default()
+                    }),
+                    15,
+                ),
+                (Behavior::Dead(DeadBehavior {}), 20),
+            ]),
+            current_behavior: CurrentBehavior(Behavior::MoveOrigo(MoveOrigoBehavior {})),
+            target_selection: TargetSelection::HighestThreat,
+            ..default()
+        }
+    }
+
+    fn create_children_spawn_params(&self) -> Vec<AnimatedChildSpawnParams> {
+        Knight.create_children_spawn_params()
+    }
+}
+
+// Shells structures and acolytes from far away, and is helpless up close
+#[derive(Component, Clone)]
+pub struct Siege;
+impl UnitChildrenSpawnParamsFactory for Siege {
+    fn unit_type(&self) -> UnitType {
+        UnitType::Siege
+    }
+
+    fn create_unit_bundle(&self) -> UnitBundle {
+        UnitBundle {
+            movement: Movement { speed: 120.0 },
+            collider: Collider { radius: 26.0 },
+            base_tint: BaseTint(Color::rgb(0.7, 0.6, 0.5)),
+            armor: Armor(2),
+            health: Health::new(150),
+            transform: Transform::from_scale(Vec3::splat(1.7)),
+            ..default()
+        }
+    }
+
+    fn create_behavior_bundle(&self) -> BehaviorBundle {
+        let attack_cooldown = 5.0;
+        let boulder = RangedAttackBehavior {
+            attack: AttackBehavior {
+                range: 420.0,
+                engage_distance: 360.0,
+                cooldown: attack_cooldown,
+                damage: 30,
+                timer: Timer::from_seconds(attack_cooldown, TimerMode::Once),
+                ..default()
+            },
+            projectile: ProjectileParams {
+                speed: 300.0,
+                lifetime: 3.0,
+                kind: DamageKind::Fire,
+                hit_radius: 14.0,
+                size: Vec2::splat(14.0),
+                color: Color::rgb(0.9, 0.4, 0.1),
+                ..default()
+            },
+        };
+
+        BehaviorBundle {
+            supported_behaviors: SupportedBehaviors(vec![
+                (Behavior::Wander(WanderBehavior::default()), 3),
+                (Behavior::MoveOrigo(MoveOrigoBehavior {}), 5),
+                (Behavior::Chase(ChaseBehavior::default()), 10),
+                (Behavior::RangedAttack(boulder), 15),
+                (Behavior::Dead(DeadBehavior {}), 20),
+            ]),
+            current_behavior: CurrentBehavior(Behavior::MoveOrigo(MoveOrigoBehavior {})),
+            target_selection: TargetSelection::PreferStructures,
+            ..default()
+        }
+    }
+
+    fn create_children_spawn_params(&self) -> Vec<AnimatedChildSpawnParams> {
+        Knight.create_children_spawn_params()
+    }
+}
+
+// Boss of the milestone waves, its later phases come from the Boss component
+#[derive(Component, Clone)]
+pub struct Warlord;
+impl UnitChildrenSpawnParamsFactory for Warlord {
+    fn unit_type(&self) -> UnitType {
+        UnitType::Warlord
+    }
+
+    fn create_unit_bundle(&self) -> UnitBundle {
+        UnitBundle {
+            movement: Movement { speed: 180.0 },
+            collider: Collider { radius: 40.0 },
+            base_tint: BaseTint(Color::rgb(0.9, 0.5, 0.5)),
+            armor: Armor(6),
+            resistances: Resistances([(DamageKind::Holy, 0.5)].into_iter().collect()),
+            health: Health::new(1200),
+            transform: Transform::from_scale(Vec3::splat(2.8)),
+            ..default()
+        }
+    }
+
+    fn create_behavior_bundle(&self) -> BehaviorBundle {
+        BehaviorBundle {
+            supported_behaviors: SupportedBehaviors(vec![
+                (Behavior::Wander(WanderBehavior::default()), 3),
+                (Behavior::MoveOrigo(MoveOrigoBehavior {}), 5),
+                (Behavior::Chase(ChaseBehavior::default()), 10),
+                (
+                    Behavior::Attack(AttackBehavior {
+                        range: 160.0,
+                        engage_distance: 120.0,
+                        cooldown: 2.5,
+                        damage: 30,
+                        ..default()
+                    }),
+                    15,
+                ),
+                (Behavior::Dead(DeadBehavior {}), 20),
+            ]),
+            current_behavior: CurrentBehavior(Behavior::MoveOrigo(MoveOrigoBehavior {})),
+            target_selection: TargetSelection::HighestThreat,
+            abilities: Abilities(vec![Ability::new(
+                "Charge",
+                10.0,
+                0.8,
+                500.0,
+                AbilityTargeting::Enemy,
+                vec![
+                    AbilityEffect::Dash { speed: 800.0 },
+                    AbilityEffect::Damage {
+                        amount: 40,
+                        kind: DamageKind::Physical,
+                    },
+                    AbilityEffect::Status(StatusEffect::stun(1.0)),
+                ],
+            )
+            .with_min_range(200.0)]),
+            ..default()
+        }
+    }
+
+    fn create_children_spawn_params(&self) -> Vec<AnimatedChildSpawnParams> {
+        Knight.create_children_spawn_params()
+    }
+}
+
 #[derive(Resource)]
 pub struct UnitResource(HashMap<UnitType, UnitConfig>);
 
@@ -314,20 +950,70 @@ impl UnitResource {
     pub fn get(&self, unit_type: UnitType) -> &UnitConfig {
         &self.0[&unit_type]
     }
+
+    // Only the units the player can summon have a config
+    pub fn try_get(&self, unit_type: UnitType) -> Option<&UnitConfig> {
+        self.0.get(&unit_type)
+    }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct UnitConfig {
-    pub cost: u8,
+    pub cost: u32,
+    pub cooldown: f32, // Seconds before another unit of the same type can be summoned
+    pub supply: u32,   // How much of the population cap the unit takes up
+    pub upkeep: u32,   // Mana drained every upkeep interval while alive, when upkeep is on
 }
 
 impl Default for UnitResource {
     fn default() -> Self {
         Self(
             [
-                (UnitType::Acolyte, UnitConfig { cost: 40 }),
-                (UnitType::Warrior, UnitConfig { cost: 30 }),
-                (UnitType::Cat, UnitConfig { cost: 20 }),
+                (
+                    UnitType::Acolyte,
+                    UnitConfig {
+                        cost: 40,
+                        cooldown: 8.0,
+                        supply: 1,
+                        upkeep: 0,
+                    },
+                ),
+                (
+                    UnitType::Warrior,
+                    UnitConfig {
+                        cost: 30,
+                        cooldown: 4.0,
+                        supply: 2,
+                        upkeep: 2,
+                    },
+                ),
+                (
+                    UnitType::Cat,
+                    UnitConfig {
+                        cost: 20,
+                        cooldown: 2.0,
+                        supply: 1,
+                        upkeep: 1,
+                    },
+                ),
+                (
+                    UnitType::Warlock,
+                    UnitConfig {
+                        cost: 35,
+                        cooldown: 5.0,
+                        supply: 2,
+                        upkeep: 2,
+                    },
+                ),
+                (
+                    UnitType::Mender,
+                    UnitConfig {
+                        cost: 30,
+                        cooldown: 6.0,
+                        supply: 1,
+                        upkeep: 1,
+                    },
+                ),
             ]
             .iter()
             .cloned()
@@ -349,7 +1035,12 @@ pub fn spawn_unit<'a>(
     unit_bundle.transform.translation = Vec3::new(spawn_position.x, spawn_position.y, 0.0);
 
     let behavior_bundle = unit_component.create_behavior_bundle();
-    let mut entity = commands.spawn((unit_bundle, behavior_bundle.clone()));
+    let mut entity = commands.spawn((
+        unit_bundle,
+        behavior_bundle.clone(),
+        unit_component.unit_type(),
+        Veterancy::default(),
+    ));
 
     behavior_bundle
         .supported_behaviors
@@ -366,18 +1057,42 @@ pub fn spawn_unit<'a>(
                 (Behavior::Wander(behavior), _) => {
                     entity.insert(behavior.clone());
                 }
+                (Behavior::Follow(behavior), _) => {
+                    entity.insert(behavior.clone());
+                }
+                (Behavior::Guard(behavior), _) => {
+                    entity.insert(*behavior);
+                }
+                (Behavior::Command(behavior), _) => {
+                    entity.insert(*behavior);
+                }
                 (Behavior::Chase(behavior), _) => {
                     entity.insert(*behavior);
                 }
                 (Behavior::Flee(behavior), _) => {
                     entity.insert(*behavior);
                 }
+                (Behavior::Heal(behavior), _) => {
+                    entity.insert(behavior.clone());
+                }
                 (Behavior::Attack(behavior), _) => {
                     entity.insert(behavior.clone());
                 }
+                (Behavior::RangedAttack(behavior), _) => {
+                    entity.insert((behavior.clone(), behavior.attack.clone()));
+                }
                 (Behavior::Dead(behavior), _) => {
                     entity.insert(behavior.clone());
                 }
+                (Behavior::Casting(behavior), _) => {
+                    entity.insert(*behavior);
+                }
+                (Behavior::Stunned(behavior), _) => {
+                    entity.insert(*behavior);
+                }
+                (Behavior::Feared(behavior), _) => {
+                    entity.insert(*behavior);
+                }
             };
         });
 
@@ -0,0 +1,123 @@
+use bevy::prelude::*;
+
+use crate::combat::damage::{UnitDamaged, UnitDied};
+use crate::units::stats::{ModifierKind, Stat, StatModifier, StatModifiers};
+
+const VETERANCY_SOURCE: &str = "veterancy";
+const EXPERIENCE_PER_KILL: u32 = 50;
+// Experience needed for each rank, a unit starts at rank 0
+const RANK_THRESHOLDS: [u32; 3] = [100, 300, 600];
+
+const RANK_TEXT_OFFSET: f32 = 30.0;
+const RANK_TEXT_COLOR: Color = Color::rgb(1.0, 0.85, 0.2);
+
+#[derive(Component, Default, Clone, Copy, Debug)]
+pub struct Veterancy {
+    pub kills: u32,
+    pub damage_dealt: u32,
+    pub rank: u32,
+}
+
+impl Veterancy {
+    pub fn experience(&self) -> u32 {
+        self.damage_dealt + self.kills * EXPERIENCE_PER_KILL
+    }
+
+    fn earned_rank(&self) -> u32 {
+        let experience = self.experience();
+        RANK_THRESHOLDS
+            .iter()
+            .filter(|threshold| experience >= **threshold)
+            .count() as u32
+    }
+}
+
+#[derive(Component)]
+pub struct RankText;
+
+pub fn track_veterancy(
+    mut damaged_events: EventReader<UnitDamaged>,
+    mut died_events: EventReader<UnitDied>,
+    mut query: Query<&mut Veterancy>,
+) {
+    for event in damaged_events.read() {
+        if let Some(mut veterancy) = event.source.and_then(|source| query.get_mut(source).ok()) {
+            veterancy.damage_dealt += event.amount;
+        }
+    }
+
+    for event in died_events.read() {
+        if let Some(mut veterancy) = event.source.and_then(|source| query.get_mut(source).ok()) {
+            veterancy.kills += 1;
+        }
+    }
+}
+
+pub fn promote_veterans(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut query: Query<(
+        Entity,
+        &mut Veterancy,
+        &mut StatModifiers,
+        Option<&Children>,
+    )>,
+    mut rank_text_query: Query<&mut Text, With<RankText>>,
+) {
+    for (entity, mut veterancy, mut stat_modifiers, children) in query.iter_mut() {
+        let rank = veterancy.earned_rank();
+        if rank <= veterancy.rank {
+            continue;
+        }
+
+        veterancy.rank = rank;
+        let rank = rank as f32;
+        stat_modifiers.add(StatModifier::new(
+            Stat::Damage,
+            ModifierKind::Multiplicative(1.0 + 0.15 * rank),
+            VETERANCY_SOURCE,
+        ));
+        stat_modifiers.add(StatModifier::new(
+            Stat::MaxHealth,
+            ModifierKind::Multiplicative(1.0 + 0.1 * rank),
+            VETERANCY_SOURCE,
+        ));
+        stat_modifiers.add(StatModifier::new(
+            Stat::AttackCooldown,
+            ModifierKind::Multiplicative(1.0 - 0.08 * rank),
+            VETERANCY_SOURCE,
+        ));
+
+        let rank_label = "*".repeat(veterancy.rank as usize);
+        let existing_text = children.and_then(|children| {
+            children
+                .iter()
+                .find(|child| rank_text_query.contains(**child))
+                .copied()
+        });
+
+        if let Some(mut text) = existing_text.and_then(|child| rank_text_query.get_mut(child).ok())
+        {
+            text.sections[0].value = rank_label;
+            continue;
+        }
+
+        commands.entity(entity).with_children(|parent| {
+            parent.spawn((
+                Text2dBundle {
+                    text: Text::from_section(
+                        rank_label,
+                        TextStyle {
+                            font: asset_server.load("fonts/JetBrainsMonoNerdFont-Regular.ttf"),
+                            font_size: 16.0,
+                            color: RANK_TEXT_COLOR,
+                        },
+                    ),
+                    transform: Transform::from_translation(Vec3::new(0.0, RANK_TEXT_OFFSET, 1.0)),
+                    ..default()
+                },
+                RankText,
+            ));
+        });
+    }
+}